@@ -0,0 +1,239 @@
+//! Measures the compute units spent placing, cancelling and claiming a bid in English auctions of
+//! 8, 64, 256 and 1024 winners, each filled with as many bids as it has winners. Every
+//! measurement is the worst case for the size of the book: the bid placed ranks below all the
+//! others, the bid cancelled is that same lowest bid, and the bid claimed is the last winner.
+//!
+//! Auctions above 64 winners are too large to create in one go, the program can only allocate 10KB
+//! at a time, so they are created with 64 winners and raised with ResizeAuction in steps of 64.
+//!
+//! Runs against the BPF build of the program, so build it first:
+//!
+//! ```sh
+//! cargo build-bpf
+//! cargo run --example compute_units
+//! ```
+
+use {
+    cw_marketplace::{
+        instruction::{
+            cancel_bid_instruction, claim_bid_instruction, create_auction_instruction,
+            end_auction_instruction, place_native_bid_instruction, resize_auction_instruction,
+            start_auction_instruction,
+        },
+        processor::{
+            CancelBidArgs, ClaimBidArgs, CreateAuctionArgs, EndAuctionArgs, PlaceBidArgs,
+            PriceFloor, ResizeAuctionArgs, StartAuctionArgs, WinnerLimit,
+        },
+        PREFIX,
+    },
+    solana_program::{instruction::Instruction, pubkey::Pubkey, system_instruction},
+    solana_program_test::{ProgramTest, ProgramTestContext},
+    solana_sdk::{
+        native_token::LAMPORTS_PER_SOL,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+    std::cmp,
+};
+
+/// Auction sizes measured, in winners.
+const WINNERS: [usize; 4] = [8, 64, 256, 1024];
+
+/// Winners added per ResizeAuction, each winner taking room for two bids of 40 bytes.
+const WINNERS_PER_STEP: usize = 64;
+
+/// Lamports given to each bidder, enough for the bid and the rent of its pot and metadata.
+const BIDDER_FUNDS: u64 = LAMPORTS_PER_SOL / 10;
+
+/// Wallets funded per transaction while filling the book.
+const TRANSFERS_PER_TRANSACTION: usize = 16;
+
+/// Sends the instructions in one transaction and returns the compute units they spent.
+async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> u64 {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+
+    let outcome = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    outcome.result.unwrap();
+    outcome.metadata.unwrap().compute_units_consumed
+}
+
+/// Returns the compute units spent placing, cancelling and claiming a bid.
+async fn measure(winners: usize) -> (u64, u64, u64) {
+    let program_id = cw_marketplace::id();
+    let mut program_test = ProgramTest::new("cw_marketplace", program_id, None);
+    program_test.prefer_bpf(true);
+    // Measured past the default budget, to show how far a size is over it.
+    program_test.set_compute_max_units(1_400_000);
+    let mut context = program_test.start_with_context().await;
+    let authority = context.payer.pubkey();
+    let resource = Pubkey::new_unique();
+
+    let initial_winners = cmp::min(winners, WINNERS_PER_STEP);
+    send(
+        &mut context,
+        &[create_auction_instruction(
+            program_id,
+            authority,
+            CreateAuctionArgs {
+                winners: WinnerLimit::Capped(initial_winners),
+                end_auction_at: None,
+                end_auction_gap: None,
+                token_mint: spl_token::native_mint::id(),
+                authority,
+                resource,
+                price_floor: PriceFloor::None([0; 32]),
+                tick_size: None,
+                gap_tick_size_percentage: None,
+            },
+        )],
+        &[],
+    )
+    .await;
+
+    for step in (initial_winners + WINNERS_PER_STEP..=winners).step_by(WINNERS_PER_STEP) {
+        send(
+            &mut context,
+            &[resize_auction_instruction(
+                program_id,
+                authority,
+                authority,
+                ResizeAuctionArgs {
+                    resource,
+                    winners: step,
+                    max_bids: None,
+                },
+            )],
+            &[],
+        )
+        .await;
+    }
+
+    send(
+        &mut context,
+        &[start_auction_instruction(
+            program_id,
+            authority,
+            StartAuctionArgs { resource },
+        )],
+        &[],
+    )
+    .await;
+
+    // One bidder more than there are winners, the last of them outbid by all the others.
+    let bidders: Vec<Keypair> = (0..=winners).map(|_| Keypair::new()).collect();
+    for chunk in bidders.chunks(TRANSFERS_PER_TRANSACTION) {
+        let transfers: Vec<Instruction> = chunk
+            .iter()
+            .map(|bidder| system_instruction::transfer(&authority, &bidder.pubkey(), BIDDER_FUNDS))
+            .collect();
+        send(&mut context, &transfers, &[]).await;
+    }
+
+    let place = |bidder: &Keypair, amount: u64| {
+        place_native_bid_instruction(
+            program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            PlaceBidArgs { amount, resource },
+        )
+    };
+    for (i, bidder) in bidders[..winners].iter().enumerate() {
+        send(&mut context, &[place(bidder, 2_000 + i as u64)], &[bidder]).await;
+    }
+
+    let lowest = &bidders[winners];
+    let place_units = send(&mut context, &[place(lowest, 1_000)], &[lowest]).await;
+
+    let (auction, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), resource.as_ref()],
+        &program_id,
+    );
+    let pot = |bidder: &Pubkey| {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                auction.as_ref(),
+                bidder.as_ref(),
+            ],
+            &program_id,
+        )
+        .0
+    };
+
+    let cancel_units = send(
+        &mut context,
+        &[cancel_bid_instruction(
+            program_id,
+            lowest.pubkey(),
+            lowest.pubkey(),
+            pot(&lowest.pubkey()),
+            spl_token::native_mint::id(),
+            CancelBidArgs { resource },
+        )],
+        &[lowest],
+    )
+    .await;
+
+    send(
+        &mut context,
+        &[end_auction_instruction(
+            program_id,
+            authority,
+            EndAuctionArgs {
+                resource,
+                reveal: None,
+            },
+        )],
+        &[],
+    )
+    .await;
+
+    let last_winner = bidders[0].pubkey();
+    let claim_units = send(
+        &mut context,
+        &[claim_bid_instruction(
+            program_id,
+            authority,
+            authority,
+            last_winner,
+            pot(&last_winner),
+            spl_token::native_mint::id(),
+            spl_token::id(),
+            None,
+            ClaimBidArgs { resource },
+        )],
+        &[],
+    )
+    .await;
+
+    (place_units, cancel_units, claim_units)
+}
+
+#[tokio::main]
+async fn main() {
+    println!(
+        "{:>8} {:>10} {:>10} {:>10}",
+        "winners", "place", "cancel", "claim"
+    );
+    for winners in WINNERS.iter() {
+        let (place, cancel, claim) = measure(*winners).await;
+        println!("{:>8} {:>10} {:>10} {:>10}", winners, place, cancel, claim);
+    }
+}
@@ -0,0 +1,678 @@
+//! Command line tool for operating auctions, built on the client module. Besides the dependencies
+//! of the program it uses clap 2, solana-client, solana-sdk and spl-token-2022:
+//!
+//! ```sh
+//! cargo run --features client,no-entrypoint --bin cw-auction -- --help
+//! ```
+//!
+//! Every subcommand that sends a transaction takes `--dry-run`, which prints the instructions and
+//! the accounts they derive instead, without reaching the cluster. A dry run pays from a throwaway
+//! key when there is no keypair file, so it works on a machine with no Solana setup at all:
+//!
+//! ```sh
+//! cargo run --features client,no-entrypoint --bin cw-auction -- \
+//!     --dry-run start --resource 11111111111111111111111111111111
+//! ```
+//!
+//! tests/cw_auction.rs runs the same dry run with `cargo test --features client,no-entrypoint`.
+
+use {
+    clap::{crate_version, App, AppSettings, Arg, ArgMatches, SubCommand},
+    cw_marketplace::{
+        client::{
+            decode_auction, decode_auction_extended, decode_bidder_pot, AuctionStatus,
+            InstructionBuilder,
+        },
+        processor::{CreateAuctionArgs, CreateAuctionArgsV2, PriceFloor, WinnerLimit},
+    },
+    solana_client::rpc_client::RpcClient,
+    solana_program::{hash, instruction::Instruction, program_pack::Pack, pubkey::Pubkey},
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        signature::{read_keypair_file, Keypair, Signer},
+        system_instruction,
+        transaction::Transaction,
+    },
+    spl_token_2022::{
+        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        state::{Account, Mint},
+    },
+    std::{error::Error, process::exit, str::FromStr},
+};
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+fn pubkey_arg<'a, 'b>(name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name("PUBKEY")
+        .takes_value(true)
+        .help(help)
+}
+
+fn number_arg<'a, 'b>(name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name("NUMBER")
+        .takes_value(true)
+        .help(help)
+}
+
+fn signer_arg<'a, 'b>(name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name("KEYPAIR")
+        .takes_value(true)
+        .help(help)
+}
+
+fn pubkey_of(matches: &ArgMatches, name: &str) -> Result<Option<Pubkey>, Box<dyn Error>> {
+    match matches.value_of(name) {
+        Some(value) => Ok(Some(Pubkey::from_str(value).map_err(|_| {
+            format!("--{} is not a valid public key: {}", name, value)
+        })?)),
+        None => Ok(None),
+    }
+}
+
+fn number_of<T>(matches: &ArgMatches, name: &str) -> Result<Option<T>, Box<dyn Error>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    match matches.value_of(name) {
+        Some(value) => {
+            Ok(Some(T::from_str(value).map_err(|e| {
+                format!("--{} is not a valid number: {}", name, e)
+            })?))
+        }
+        None => Ok(None),
+    }
+}
+
+fn required_pubkey(matches: &ArgMatches, name: &str) -> Result<Pubkey, Box<dyn Error>> {
+    pubkey_of(matches, name)?.ok_or_else(|| format!("--{} is required", name).into())
+}
+
+fn keypair_of(matches: &ArgMatches, name: &str) -> Result<Option<Keypair>, Box<dyn Error>> {
+    match matches.value_of(name) {
+        Some(path) => {
+            Ok(Some(read_keypair_file(path).map_err(|e| {
+                format!("Could not read keypair from {}: {}", path, e)
+            })?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Where to send transactions and who signs and pays for them.
+struct Config {
+    rpc: RpcClient,
+    payer: Keypair,
+    program_id: Pubkey,
+    token_program: Pubkey,
+    dry_run: bool,
+}
+
+impl Config {
+    fn builder(&self, resource: Pubkey) -> InstructionBuilder {
+        InstructionBuilder::new(self.program_id, resource)
+            .payer(self.payer.pubkey())
+            .token_program(self.token_program)
+    }
+
+    /// Size of a token account of the mint, with room for the extensions a Token-2022 mint
+    /// requires of its accounts. A dry run does not read the mint and assumes none.
+    fn token_account_len(&self, mint: &Pubkey) -> Result<usize, Box<dyn Error>> {
+        if self.token_program == spl_token::id() || self.dry_run {
+            return Ok(Account::LEN);
+        }
+        let data = self.rpc.get_account_data(mint)?;
+        let mint_extensions = StateWithExtensions::<Mint>::unpack(&data)?.get_extension_types()?;
+        let account_extensions =
+            ExtensionType::get_required_init_account_extensions(&mint_extensions);
+        Ok(ExtensionType::try_calculate_account_len::<Account>(
+            &account_extensions,
+        )?)
+    }
+
+    /// Sends the instructions in one transaction, or prints them and the accounts derived for the
+    /// auction on a dry run.
+    fn send(
+        &self,
+        builder: &InstructionBuilder,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> CliResult {
+        if self.dry_run {
+            let pdas = builder.pdas();
+            println!("Program:          {}", pdas.program_id);
+            println!("Resource:         {}", pdas.resource);
+            println!("Auction:          {}", pdas.auction());
+            println!("Auction extended: {}", pdas.auction_extended());
+            for instruction in instructions {
+                println!();
+                println!("Instruction for {}", instruction.program_id);
+                for (i, account) in instruction.accounts.iter().enumerate() {
+                    println!(
+                        "  {:>2}. {} {}{}",
+                        i,
+                        account.pubkey,
+                        if account.is_writable { "w" } else { "-" },
+                        if account.is_signer { "s" } else { "-" },
+                    );
+                }
+                println!("  data: {}", hex(&instruction.data));
+            }
+            return Ok(());
+        }
+
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+        println!("Signature: {}", signature);
+        Ok(())
+    }
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn price_floor(matches: &ArgMatches) -> Result<PriceFloor, Box<dyn Error>> {
+    if let Some(price) = number_of(matches, "minimum-price")? {
+        return Ok(PriceFloor::MinimumPrice([price, 0, 0, 0]));
+    }
+    if let Some(price) = number_of::<u64>(matches, "blinded-price")? {
+        let salt = number_of::<u64>(matches, "salt")?.ok_or("--salt is required")?;
+        let blinded = hash::hashv(&[&price.to_be_bytes(), &salt.to_be_bytes()]);
+        return Ok(PriceFloor::BlindedPrice(blinded));
+    }
+    Ok(PriceFloor::None([0; 32]))
+}
+
+fn create(config: &Config, matches: &ArgMatches) -> CliResult {
+    let resource = required_pubkey(matches, "resource")?;
+    let winners = match number_of(matches, "winners")? {
+        Some(n) => WinnerLimit::Capped(n),
+        None => WinnerLimit::Unlimited(0),
+    };
+    let token_mint = pubkey_of(matches, "mint")?.unwrap_or_else(spl_token::native_mint::id);
+    let authority = pubkey_of(matches, "authority")?.unwrap_or_else(|| config.payer.pubkey());
+    let end_auction_at = number_of::<i64>(matches, "end-auction-at")?;
+    let end_auction_gap = number_of::<i64>(matches, "end-auction-gap")?;
+    let tick_size = number_of::<u64>(matches, "tick-size")?;
+    let gap_tick_size_percentage = number_of::<u8>(matches, "gap-tick-size")?;
+    let price_floor = price_floor(matches)?;
+
+    let builder = config.builder(resource);
+    let instruction = if matches.is_present("v2") {
+        let name = matches.value_of("name").map(|name| {
+            let mut padded = [0u8; 32];
+            let len = name.len().min(32);
+            padded[..len].copy_from_slice(&name.as_bytes()[..len]);
+            padded
+        });
+        builder.create_auction_v2(CreateAuctionArgsV2 {
+            winners,
+            end_auction_at,
+            end_auction_gap,
+            token_mint,
+            authority,
+            resource,
+            price_floor,
+            tick_size,
+            gap_tick_size_percentage,
+            instant_sale_price: number_of::<u64>(matches, "instant-sale-price")?,
+            name,
+            auction_type: None,
+        })?
+    } else {
+        builder.create_auction(CreateAuctionArgs {
+            winners,
+            end_auction_at,
+            end_auction_gap,
+            token_mint,
+            authority,
+            resource,
+            price_floor,
+            tick_size,
+            gap_tick_size_percentage,
+        })?
+    };
+    config.send(&builder, &[instruction], &[])
+}
+
+/// The authority keypair given, or the payer.
+fn signer_or_payer<'a>(config: &'a Config, keypair: &'a Option<Keypair>) -> &'a Keypair {
+    keypair.as_ref().unwrap_or(&config.payer)
+}
+
+fn start(config: &Config, matches: &ArgMatches) -> CliResult {
+    let keypair = keypair_of(matches, "authority")?;
+    let authority = signer_or_payer(config, &keypair);
+    let builder = config
+        .builder(required_pubkey(matches, "resource")?)
+        .authority(authority.pubkey());
+    config.send(&builder, &[builder.start_auction()?], &[authority])
+}
+
+fn end(config: &Config, matches: &ArgMatches) -> CliResult {
+    let keypair = keypair_of(matches, "authority")?;
+    let authority = signer_or_payer(config, &keypair);
+    let reveal = match number_of(matches, "reveal-price")? {
+        Some(price) => Some((
+            price,
+            number_of(matches, "reveal-salt")?.ok_or("--reveal-salt is required")?,
+        )),
+        None => None,
+    };
+    let builder = config
+        .builder(required_pubkey(matches, "resource")?)
+        .authority(authority.pubkey());
+    config.send(&builder, &[builder.end_auction(reveal)?], &[authority])
+}
+
+fn set_authority(config: &Config, matches: &ArgMatches) -> CliResult {
+    let keypair = keypair_of(matches, "authority")?;
+    let authority = signer_or_payer(config, &keypair);
+    let builder = config
+        .builder(required_pubkey(matches, "resource")?)
+        .authority(authority.pubkey());
+    let instruction = builder.set_authority(required_pubkey(matches, "new-authority")?)?;
+    config.send(&builder, &[instruction], &[authority])
+}
+
+/// Sets the token account holding the bid on SPL token auctions. Without one given it is read from
+/// the bidder pot, which a dry run cannot do.
+fn with_pot_token(
+    config: &Config,
+    matches: &ArgMatches,
+    builder: InstructionBuilder,
+    bidder: &Pubkey,
+    mint: &Pubkey,
+) -> Result<InstructionBuilder, Box<dyn Error>> {
+    if *mint == spl_token::native_mint::id() {
+        return Ok(builder);
+    }
+    let pot_token = match pubkey_of(matches, "pot-token")? {
+        Some(pot_token) => pot_token,
+        None if config.dry_run => return Err("--pot-token is required on a dry run".into()),
+        None => {
+            let pot = builder.pdas().bidder_pot(bidder);
+            decode_bidder_pot(&config.rpc.get_account_data(&pot)?)?.bidder_pot
+        }
+    };
+    Ok(builder.bidder_pot_token(pot_token))
+}
+
+fn place_bid(config: &Config, matches: &ArgMatches) -> CliResult {
+    let keypair = keypair_of(matches, "bidder")?;
+    let bidder = signer_or_payer(config, &keypair);
+    let amount = number_of::<u64>(matches, "amount")?.ok_or("--amount is required")?;
+    let mint = pubkey_of(matches, "mint")?.unwrap_or_else(spl_token::native_mint::id);
+    let mut builder = config
+        .builder(required_pubkey(matches, "resource")?)
+        .bidder(bidder.pubkey())
+        .token_mint(mint);
+    if let Some(bidder_token) = pubkey_of(matches, "bidder-token")? {
+        builder = builder.bidder_token(bidder_token);
+    }
+
+    // The first bid of an SPL token auction needs a token account owned by the auction to hold it.
+    let mut instructions = vec![];
+    let pot_token = Keypair::new();
+    let mut signers = vec![bidder];
+    if mint != spl_token::native_mint::id() {
+        match pubkey_of(matches, "pot-token")? {
+            Some(existing) => builder = builder.bidder_pot_token(existing),
+            None => {
+                let len = config.token_account_len(&mint)?;
+                let rent = if config.dry_run {
+                    0
+                } else {
+                    config.rpc.get_minimum_balance_for_rent_exemption(len)?
+                };
+                instructions.push(system_instruction::create_account(
+                    &config.payer.pubkey(),
+                    &pot_token.pubkey(),
+                    rent,
+                    len as u64,
+                    &config.token_program,
+                ));
+                instructions.push(spl_token_2022::instruction::initialize_account3(
+                    &config.token_program,
+                    &pot_token.pubkey(),
+                    &mint,
+                    &builder.pdas().auction(),
+                )?);
+                builder = builder.bidder_pot_token(pot_token.pubkey());
+                signers.push(&pot_token);
+            }
+        }
+    }
+
+    instructions.push(builder.place_bid(amount)?);
+    config.send(&builder, &instructions, &signers)
+}
+
+fn cancel_bid(config: &Config, matches: &ArgMatches) -> CliResult {
+    let keypair = keypair_of(matches, "bidder")?;
+    let bidder = signer_or_payer(config, &keypair);
+    let mint = pubkey_of(matches, "mint")?.unwrap_or_else(spl_token::native_mint::id);
+    let mut builder = config
+        .builder(required_pubkey(matches, "resource")?)
+        .bidder(bidder.pubkey())
+        .token_mint(mint);
+    if let Some(bidder_token) = pubkey_of(matches, "bidder-token")? {
+        builder = builder.bidder_token(bidder_token);
+    }
+    let builder = with_pot_token(config, matches, builder, &bidder.pubkey(), &mint)?;
+    config.send(&builder, &[builder.cancel_bid()?], &[bidder])
+}
+
+fn claim_bid(config: &Config, matches: &ArgMatches) -> CliResult {
+    let keypair = keypair_of(matches, "authority")?;
+    let authority = signer_or_payer(config, &keypair);
+    let bidder = required_pubkey(matches, "bidder")?;
+    let mint = pubkey_of(matches, "mint")?.unwrap_or_else(spl_token::native_mint::id);
+    let builder = config
+        .builder(required_pubkey(matches, "resource")?)
+        .authority(authority.pubkey())
+        .bidder(bidder)
+        .token_mint(mint)
+        .destination(required_pubkey(matches, "destination")?);
+    let builder = with_pot_token(config, matches, builder, &bidder, &mint)?;
+    config.send(&builder, &[builder.claim_bid()?], &[authority])
+}
+
+fn show(config: &Config, matches: &ArgMatches) -> CliResult {
+    let builder = config.builder(required_pubkey(matches, "resource")?);
+    let pdas = builder.pdas();
+    let auction = decode_auction(&config.rpc.get_account_data(&pdas.auction())?)?;
+    let now = config.rpc.get_block_time(config.rpc.get_slot()?)?;
+    let status = AuctionStatus::at(&auction, now)?;
+
+    println!("Auction:          {}", pdas.auction());
+    println!("Authority:        {}", auction.authority);
+    println!("Token mint:       {}", auction.token_mint);
+    println!("State:            {:?}", auction.state);
+    println!("Ended now:        {}", status.ended);
+    println!("Ended at:         {:?}", auction.ended_at);
+    println!("End auction at:   {:?}", auction.end_auction_at);
+    println!("End auction gap:  {:?}", auction.end_auction_gap);
+    println!("Last bid:         {:?}", auction.last_bid);
+    println!("Price floor:      {:?}", auction.price_floor);
+    println!(
+        "Winners:          {} of {}",
+        auction.num_winners(),
+        auction.num_possible_winners()
+    );
+    match &status.leader {
+        Some(leader) => println!("Leader:           {} with {}", leader.0, leader.1),
+        None => println!("Leader:           none"),
+    }
+    for (rank, bid) in status.winners.iter().enumerate() {
+        println!("  {:>4}. {} {}", rank + 1, bid.0, bid.1);
+    }
+
+    match config.rpc.get_account_data(&pdas.auction_extended()) {
+        Ok(data) => println!("\n{:#?}", decode_auction_extended(&data)?),
+        Err(_) => println!("\nNo auction extended account"),
+    }
+    Ok(())
+}
+
+fn main() {
+    let resource_arg = pubkey_arg("resource", "The resource being auctioned").required(true);
+    let bid_token_args = vec![
+        pubkey_arg("mint", "Token mint of the auction, native SOL if not given"),
+        pubkey_arg(
+            "bidder-token",
+            "Token account the bid is paid from, the bidder on native SOL auctions",
+        ),
+        pubkey_arg(
+            "pot-token",
+            "Token account holding the bid, read from the bidder pot if not given",
+        ),
+    ];
+
+    let matches = App::new("cw-auction")
+        .version(crate_version!())
+        .about("Operate auctions of the auction program")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .global(true)
+                .default_value("http://localhost:8899")
+                .help("JSON RPC URL of the cluster"),
+        )
+        .arg(
+            signer_arg(
+                "keypair",
+                "Keypair file of the fee payer, the default Solana CLI keypair if not given",
+            )
+            .global(true),
+        )
+        .arg(pubkey_arg("program-id", "Address of the auction program").global(true))
+        .arg(
+            pubkey_arg(
+                "token-program",
+                "Token program of the auction mint, the original token program if not given",
+            )
+            .global(true),
+        )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .global(true)
+                .help("Print the instructions and derived accounts instead of sending them"),
+        )
+        .subcommand(
+            SubCommand::with_name("create")
+                .about("Create an auction")
+                .arg(resource_arg.clone())
+                .arg(number_arg(
+                    "winners",
+                    "Number of winners, unlimited if not given",
+                ))
+                .arg(pubkey_arg(
+                    "mint",
+                    "Token mint bids are paid in, native SOL if not given",
+                ))
+                .arg(pubkey_arg(
+                    "authority",
+                    "Authority of the auction, the payer if not given",
+                ))
+                .arg(number_arg(
+                    "end-auction-at",
+                    "Seconds after the start the auction ends",
+                ))
+                .arg(number_arg(
+                    "end-auction-gap",
+                    "Seconds after the last bid the auction ends",
+                ))
+                .arg(number_arg(
+                    "tick-size",
+                    "Bids must be a multiple of this amount",
+                ))
+                .arg(number_arg(
+                    "gap-tick-size",
+                    "Percentage each bid must beat the last by",
+                ))
+                .arg(number_arg("minimum-price", "Lowest bid that can win"))
+                .arg(
+                    number_arg(
+                        "blinded-price",
+                        "Lowest bid that can win, hidden until the end",
+                    )
+                    .conflicts_with("minimum-price")
+                    .requires("salt"),
+                )
+                .arg(number_arg("salt", "Salt the blinded price is hashed with"))
+                .arg(
+                    Arg::with_name("v2")
+                        .long("v2")
+                        .help("Create with CreateAuctionV2, taking a name and instant sale price"),
+                )
+                .arg(number_arg("instant-sale-price", "Bid that wins at once").requires("v2"))
+                .arg(
+                    Arg::with_name("name")
+                        .long("name")
+                        .value_name("NAME")
+                        .takes_value(true)
+                        .requires("v2")
+                        .help("Name of the auction, up to 32 bytes"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("start")
+                .about("Start an auction")
+                .arg(resource_arg.clone())
+                .arg(signer_arg(
+                    "authority",
+                    "Authority keypair, the payer if not given",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("end")
+                .about("End an auction")
+                .arg(resource_arg.clone())
+                .arg(signer_arg(
+                    "authority",
+                    "Authority keypair, the payer if not given",
+                ))
+                .arg(
+                    number_arg("reveal-price", "Blinded price floor to reveal")
+                        .requires("reveal-salt"),
+                )
+                .arg(number_arg(
+                    "reveal-salt",
+                    "Salt the blinded price was hashed with",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("set-authority")
+                .about("Hand an auction over to a new authority")
+                .arg(resource_arg.clone())
+                .arg(signer_arg(
+                    "authority",
+                    "Authority keypair, the payer if not given",
+                ))
+                .arg(pubkey_arg("new-authority", "The new authority").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("place-bid")
+                .about("Place a bid")
+                .arg(resource_arg.clone())
+                .arg(signer_arg(
+                    "bidder",
+                    "Bidder keypair, the payer if not given",
+                ))
+                .arg(number_arg("amount", "Amount to bid").required(true))
+                .args(&bid_token_args),
+        )
+        .subcommand(
+            SubCommand::with_name("cancel-bid")
+                .about("Cancel a bid and take it back")
+                .arg(resource_arg.clone())
+                .arg(signer_arg(
+                    "bidder",
+                    "Bidder keypair, the payer if not given",
+                ))
+                .args(&bid_token_args),
+        )
+        .subcommand(
+            SubCommand::with_name("claim-bid")
+                .about("Claim a winning bid into the destination")
+                .arg(resource_arg.clone())
+                .arg(signer_arg(
+                    "authority",
+                    "Authority keypair, the payer if not given",
+                ))
+                .arg(pubkey_arg("bidder", "The winning bidder").required(true))
+                .arg(pubkey_arg("destination", "Account the bid is paid into").required(true))
+                .arg(pubkey_arg(
+                    "mint",
+                    "Token mint of the auction, native SOL if not given",
+                ))
+                .arg(pubkey_arg(
+                    "pot-token",
+                    "Token account holding the bid, read from the bidder pot if not given",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Print the state, bids and extended data of an auction")
+                .arg(resource_arg),
+        )
+        .get_matches();
+
+    // Global arguments are passed down to the subcommand wherever they were given.
+    let (command, sub_matches) = matches.subcommand();
+    let sub_matches = sub_matches.unwrap();
+
+    let result = (|| -> CliResult {
+        let keypair_path = match sub_matches.value_of("keypair") {
+            Some(path) => path.to_string(),
+            None => format!(
+                "{}/.config/solana/id.json",
+                std::env::var("HOME").unwrap_or_default()
+            ),
+        };
+        let dry_run = sub_matches.is_present("dry-run");
+        // A dry run signs nothing, so it does not need a keypair on disk.
+        let payer = match read_keypair_file(&keypair_path) {
+            Ok(payer) => payer,
+            Err(_) if dry_run => {
+                let payer = Keypair::new();
+                eprintln!(
+                    "No keypair at {}, paying from throwaway key {}",
+                    keypair_path,
+                    payer.pubkey()
+                );
+                payer
+            }
+            Err(e) => {
+                return Err(format!("Could not read keypair from {}: {}", keypair_path, e).into())
+            }
+        };
+        let config = Config {
+            rpc: RpcClient::new_with_commitment(
+                sub_matches.value_of("url").unwrap().to_string(),
+                CommitmentConfig::confirmed(),
+            ),
+            payer,
+            program_id: pubkey_of(sub_matches, "program-id")?.unwrap_or_else(cw_marketplace::id),
+            token_program: pubkey_of(sub_matches, "token-program")?.unwrap_or_else(spl_token::id),
+            dry_run,
+        };
+
+        match command {
+            "create" => create(&config, sub_matches),
+            "start" => start(&config, sub_matches),
+            "end" => end(&config, sub_matches),
+            "set-authority" => set_authority(&config, sub_matches),
+            "place-bid" => place_bid(&config, sub_matches),
+            "cancel-bid" => cancel_bid(&config, sub_matches),
+            "claim-bid" => claim_bid(&config, sub_matches),
+            "show" => show(&config, sub_matches),
+            _ => unreachable!(),
+        }
+    })();
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        exit(1);
+    }
+}
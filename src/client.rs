@@ -0,0 +1,502 @@
+//! Helpers for programs and services that talk to the auction program from off chain: the
+//! addresses of its accounts, instructions built from them, and its accounts read back from their
+//! data. Only compiled with the `client` feature, which is meant to be used together with
+//! `no-entrypoint`.
+//!
+//! Instructions are built by an InstructionBuilder for one auction. The wallets an instruction
+//! needs are set on the builder first, and building fails rather than panics when one is missing.
+
+use crate::{
+    instruction::AuctionInstruction,
+    processor::{
+        AuctionData, AuctionDataExtended, Bid, BidderMetadata, BidderPot, CancelAuctionArgs,
+        CancelBidArgs, ClaimBidArgs, CreateAuctionArgs, CreateAuctionArgsV2, EndAuctionArgs, Key,
+        PlaceBidArgs, PriceFloor, StartAuctionArgs, BASE_AUCTION_DATA_SIZE, BIDDER_METADATA_LEN,
+        BIDDER_POT_LEN, MAX_AUCTION_DATA_EXTENDED_SIZE,
+    },
+    EXTENDED, FEE_SPLITS, PREFIX,
+};
+
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        borsh::try_from_slice_unchecked,
+        clock::UnixTimestamp,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program, sysvar,
+    },
+    std::mem,
+    thiserror::Error,
+};
+
+/// Errors building instructions or reading accounts of the Auction program.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The instruction needs an account that was not set on the builder
+    #[error("The instruction needs the {0} account")]
+    MissingAccount(&'static str),
+
+    /// Arguments name another resource than the builder
+    #[error("Arguments name another resource than the builder")]
+    ResourceMismatch,
+
+    /// Account data is not of the type being read
+    #[error("Account data is not a {0}")]
+    DataTypeMismatch(&'static str),
+
+    /// Data could not be serialized or deserialized
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Error computing a field the way the program does
+    #[error(transparent)]
+    Program(#[from] ProgramError),
+}
+
+/// Addresses of the accounts the program derives for one auction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pdas {
+    pub program_id: Pubkey,
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+impl Pdas {
+    pub fn new(program_id: Pubkey, resource: Pubkey) -> Self {
+        Pdas {
+            program_id,
+            resource,
+        }
+    }
+
+    fn find(&self, seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &self.program_id).0
+    }
+
+    /// [seed of ['auction', program_id, resource key]]
+    pub fn auction(&self) -> Pubkey {
+        self.find(&[
+            PREFIX.as_bytes(),
+            self.program_id.as_ref(),
+            self.resource.as_ref(),
+        ])
+    }
+
+    /// [seed of ['auction', program_id, resource key, 'extended']]
+    pub fn auction_extended(&self) -> Pubkey {
+        self.find(&[
+            PREFIX.as_bytes(),
+            self.program_id.as_ref(),
+            self.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ])
+    }
+
+    /// [seed of ['auction', program_id, resource key, 'splits']]
+    pub fn fee_splits(&self) -> Pubkey {
+        self.find(&[
+            PREFIX.as_bytes(),
+            self.program_id.as_ref(),
+            self.resource.as_ref(),
+            FEE_SPLITS.as_bytes(),
+        ])
+    }
+
+    /// [seed of ['auction', program_id, auction key, bidder key]]
+    pub fn bidder_pot(&self, bidder: &Pubkey) -> Pubkey {
+        self.find(&[
+            PREFIX.as_bytes(),
+            self.program_id.as_ref(),
+            self.auction().as_ref(),
+            bidder.as_ref(),
+        ])
+    }
+
+    /// [seed of ['auction', program_id, auction key, bidder key, 'metadata']]
+    pub fn bidder_metadata(&self, bidder: &Pubkey) -> Pubkey {
+        self.find(&[
+            PREFIX.as_bytes(),
+            self.program_id.as_ref(),
+            self.auction().as_ref(),
+            bidder.as_ref(),
+            "metadata".as_bytes(),
+        ])
+    }
+
+    /// [seed of ['auction', program_id, auction key, bidder key, 'commitment']]
+    pub fn bidder_commitment(&self, bidder: &Pubkey) -> Pubkey {
+        self.find(&[
+            PREFIX.as_bytes(),
+            self.program_id.as_ref(),
+            self.auction().as_ref(),
+            bidder.as_ref(),
+            "commitment".as_bytes(),
+        ])
+    }
+}
+
+/// Builds instructions for one auction from the wallets set on it. On native SOL auctions the
+/// bidder token account defaults to the bidder and the pot token account to the pot.
+#[derive(Clone, Debug)]
+pub struct InstructionBuilder {
+    pdas: Pdas,
+    authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    bidder: Option<Pubkey>,
+    bidder_token: Option<Pubkey>,
+    bidder_pot_token: Option<Pubkey>,
+    token_mint: Option<Pubkey>,
+    transfer_authority: Option<Pubkey>,
+    destination: Option<Pubkey>,
+    token_program: Pubkey,
+}
+
+fn required(key: Option<Pubkey>, name: &'static str) -> Result<Pubkey, ClientError> {
+    key.ok_or(ClientError::MissingAccount(name))
+}
+
+impl InstructionBuilder {
+    pub fn new(program_id: Pubkey, resource: Pubkey) -> Self {
+        InstructionBuilder {
+            pdas: Pdas::new(program_id, resource),
+            authority: None,
+            payer: None,
+            bidder: None,
+            bidder_token: None,
+            bidder_pot_token: None,
+            token_mint: None,
+            transfer_authority: None,
+            destination: None,
+            token_program: spl_token::id(),
+        }
+    }
+
+    pub fn pdas(&self) -> &Pdas {
+        &self.pdas
+    }
+
+    pub fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    pub fn bidder(mut self, bidder: Pubkey) -> Self {
+        self.bidder = Some(bidder);
+        self
+    }
+
+    pub fn bidder_token(mut self, bidder_token: Pubkey) -> Self {
+        self.bidder_token = Some(bidder_token);
+        self
+    }
+
+    pub fn bidder_pot_token(mut self, bidder_pot_token: Pubkey) -> Self {
+        self.bidder_pot_token = Some(bidder_pot_token);
+        self
+    }
+
+    pub fn token_mint(mut self, token_mint: Pubkey) -> Self {
+        self.token_mint = Some(token_mint);
+        self
+    }
+
+    /// Signs the transfer of the bid out of the bidder token account, the bidder if not set.
+    pub fn transfer_authority(mut self, transfer_authority: Pubkey) -> Self {
+        self.transfer_authority = Some(transfer_authority);
+        self
+    }
+
+    pub fn destination(mut self, destination: Pubkey) -> Self {
+        self.destination = Some(destination);
+        self
+    }
+
+    /// Token program of the mint, the original token program if not set.
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    fn native(&self) -> bool {
+        self.token_mint == Some(spl_token::native_mint::id())
+    }
+
+    fn bidder_accounts(&self) -> Result<(Pubkey, Pubkey, Pubkey, Pubkey), ClientError> {
+        let bidder = required(self.bidder, "bidder")?;
+        let mint = required(self.token_mint, "token mint")?;
+        let pot = self.pdas.bidder_pot(&bidder);
+        let (bidder_token, bidder_pot_token) = if self.native() {
+            (
+                self.bidder_token.unwrap_or(bidder),
+                self.bidder_pot_token.unwrap_or(pot),
+            )
+        } else {
+            (
+                required(self.bidder_token, "bidder token")?,
+                required(self.bidder_pot_token, "bidder pot token")?,
+            )
+        };
+        Ok((bidder, bidder_token, bidder_pot_token, mint))
+    }
+
+    fn instruction(
+        &self,
+        accounts: Vec<AccountMeta>,
+        instruction: AuctionInstruction,
+    ) -> Result<Instruction, ClientError> {
+        Ok(Instruction {
+            program_id: self.pdas.program_id,
+            accounts,
+            data: instruction.try_to_vec()?,
+        })
+    }
+
+    /// CreateAuction, paid for by the payer.
+    pub fn create_auction(&self, args: CreateAuctionArgs) -> Result<Instruction, ClientError> {
+        if args.resource != self.pdas.resource {
+            return Err(ClientError::ResourceMismatch);
+        }
+        self.instruction(
+            vec![
+                AccountMeta::new(required(self.payer, "payer")?, true),
+                AccountMeta::new(self.pdas.auction(), false),
+                AccountMeta::new(self.pdas.auction_extended(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            AuctionInstruction::CreateAuction(args),
+        )
+    }
+
+    /// CreateAuctionV2, paid for by the payer.
+    pub fn create_auction_v2(&self, args: CreateAuctionArgsV2) -> Result<Instruction, ClientError> {
+        if args.resource != self.pdas.resource {
+            return Err(ClientError::ResourceMismatch);
+        }
+        self.instruction(
+            vec![
+                AccountMeta::new(required(self.payer, "payer")?, true),
+                AccountMeta::new(self.pdas.auction(), false),
+                AccountMeta::new(self.pdas.auction_extended(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            AuctionInstruction::CreateAuctionV2(args),
+        )
+    }
+
+    pub fn set_authority(&self, new_authority: Pubkey) -> Result<Instruction, ClientError> {
+        self.instruction(
+            vec![
+                AccountMeta::new(self.pdas.auction(), false),
+                AccountMeta::new_readonly(required(self.authority, "authority")?, true),
+                AccountMeta::new_readonly(new_authority, false),
+            ],
+            AuctionInstruction::SetAuthority,
+        )
+    }
+
+    pub fn start_auction(&self) -> Result<Instruction, ClientError> {
+        self.instruction(
+            vec![
+                AccountMeta::new(required(self.authority, "authority")?, true),
+                AccountMeta::new(self.pdas.auction(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(self.pdas.auction_extended(), false),
+            ],
+            AuctionInstruction::StartAuction(StartAuctionArgs {
+                resource: self.pdas.resource,
+            }),
+        )
+    }
+
+    /// EndAuction, revealing the price and salt of a blinded price floor if there is one.
+    pub fn end_auction(&self, reveal: Option<(u64, u64)>) -> Result<Instruction, ClientError> {
+        self.instruction(
+            vec![
+                AccountMeta::new(required(self.authority, "authority")?, true),
+                AccountMeta::new(self.pdas.auction(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(self.pdas.auction_extended(), false),
+            ],
+            AuctionInstruction::EndAuction(EndAuctionArgs {
+                resource: self.pdas.resource,
+                reveal,
+            }),
+        )
+    }
+
+    pub fn cancel_auction(&self) -> Result<Instruction, ClientError> {
+        self.instruction(
+            vec![
+                AccountMeta::new_readonly(required(self.authority, "authority")?, true),
+                AccountMeta::new(self.pdas.auction(), false),
+                AccountMeta::new_readonly(self.pdas.auction_extended(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+            ],
+            AuctionInstruction::CancelAuction(CancelAuctionArgs {
+                resource: self.pdas.resource,
+            }),
+        )
+    }
+
+    /// PlaceBid of the bidder, the payer covering the rent of its pot and metadata.
+    pub fn place_bid(&self, amount: u64) -> Result<Instruction, ClientError> {
+        let (bidder, bidder_token, bidder_pot_token, mint) = self.bidder_accounts()?;
+        self.instruction(
+            vec![
+                AccountMeta::new(bidder, true),
+                AccountMeta::new(bidder_token, false),
+                AccountMeta::new(self.pdas.bidder_pot(&bidder), false),
+                AccountMeta::new(bidder_pot_token, false),
+                AccountMeta::new(self.pdas.bidder_metadata(&bidder), false),
+                AccountMeta::new(self.pdas.auction(), false),
+                AccountMeta::new(self.pdas.auction_extended(), false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(self.transfer_authority.unwrap_or(bidder), true),
+                AccountMeta::new_readonly(required(self.payer, "payer")?, true),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(self.token_program, false),
+            ],
+            AuctionInstruction::PlaceBid(PlaceBidArgs {
+                amount,
+                resource: self.pdas.resource,
+            }),
+        )
+    }
+
+    pub fn cancel_bid(&self) -> Result<Instruction, ClientError> {
+        let (bidder, bidder_token, bidder_pot_token, mint) = self.bidder_accounts()?;
+        self.instruction(
+            vec![
+                AccountMeta::new(bidder, true),
+                AccountMeta::new(bidder_token, false),
+                AccountMeta::new(self.pdas.bidder_pot(&bidder), false),
+                AccountMeta::new(bidder_pot_token, false),
+                AccountMeta::new(self.pdas.bidder_metadata(&bidder), false),
+                AccountMeta::new(self.pdas.auction(), false),
+                AccountMeta::new(self.pdas.auction_extended(), false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new(self.pdas.bidder_commitment(&bidder), false),
+            ],
+            AuctionInstruction::CancelBid(CancelBidArgs {
+                resource: self.pdas.resource,
+            }),
+        )
+    }
+
+    /// ClaimBid of the bidder into the destination, for auctions created without fee splits.
+    pub fn claim_bid(&self) -> Result<Instruction, ClientError> {
+        let (bidder, _, bidder_pot_token, mint) = self.bidder_accounts()?;
+        self.instruction(
+            vec![
+                AccountMeta::new(required(self.destination, "destination")?, false),
+                AccountMeta::new(bidder_pot_token, false),
+                AccountMeta::new(self.pdas.bidder_pot(&bidder), false),
+                AccountMeta::new_readonly(required(self.authority, "authority")?, true),
+                AccountMeta::new_readonly(self.pdas.auction(), false),
+                AccountMeta::new_readonly(bidder, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new(self.pdas.auction_extended(), false),
+                AccountMeta::new_readonly(self.pdas.bidder_commitment(&bidder), false),
+                AccountMeta::new_readonly(self.pdas.bidder_metadata(&bidder), false),
+            ],
+            AuctionInstruction::ClaimBid(ClaimBidArgs {
+                resource: self.pdas.resource,
+            }),
+        )
+    }
+}
+
+fn assert_key(data: &[u8], key: Key, name: &'static str) -> Result<(), ClientError> {
+    if data.first() != Some(&(key as u8)) {
+        return Err(ClientError::DataTypeMismatch(name));
+    }
+    Ok(())
+}
+
+pub fn decode_auction(data: &[u8]) -> Result<AuctionData, ClientError> {
+    if data.len() < BASE_AUCTION_DATA_SIZE
+        || (data.len() - BASE_AUCTION_DATA_SIZE) % mem::size_of::<Bid>() != 0
+    {
+        return Err(ClientError::DataTypeMismatch("auction"));
+    }
+    assert_key(data, Key::AuctionV1, "auction")?;
+    Ok(try_from_slice_unchecked(data)?)
+}
+
+pub fn decode_auction_extended(data: &[u8]) -> Result<AuctionDataExtended, ClientError> {
+    if data.len() != MAX_AUCTION_DATA_EXTENDED_SIZE {
+        return Err(ClientError::DataTypeMismatch("auction extended"));
+    }
+    assert_key(data, Key::AuctionExtendedV1, "auction extended")?;
+    Ok(try_from_slice_unchecked(data)?)
+}
+
+pub fn decode_bidder_metadata(data: &[u8]) -> Result<BidderMetadata, ClientError> {
+    if data.len() != BIDDER_METADATA_LEN {
+        return Err(ClientError::DataTypeMismatch("bidder metadata"));
+    }
+    assert_key(data, Key::BidderMetadataV1, "bidder metadata")?;
+    Ok(try_from_slice_unchecked(data)?)
+}
+
+pub fn decode_bidder_pot(data: &[u8]) -> Result<BidderPot, ClientError> {
+    if data.len() != BIDDER_POT_LEN {
+        return Err(ClientError::DataTypeMismatch("bidder pot"));
+    }
+    assert_key(data, Key::BidderPotV1, "bidder pot")?;
+    Ok(try_from_slice_unchecked(data)?)
+}
+
+/// An auction as the program sees it at a given time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuctionStatus {
+    /// Highest winning bid, None until somebody bids at least the minimum price.
+    pub leader: Option<Bid>,
+    /// Bids winning at this time, highest first.
+    pub winners: Vec<Bid>,
+    /// Whether the auction is over at this time.
+    pub ended: bool,
+    /// Lowest bid that can win, None while the price floor is blinded.
+    pub minimum_price: Option<u64>,
+}
+
+impl AuctionStatus {
+    pub fn at(auction: &AuctionData, now: UnixTimestamp) -> Result<AuctionStatus, ClientError> {
+        let winners: Vec<Bid> = (0..auction.num_possible_winners() as usize)
+            .filter_map(|idx| {
+                auction
+                    .winner_at(idx)
+                    .map(|key| Bid(key, auction.bid_state.amount(idx)))
+            })
+            .filter(|bid| auction.is_winner(&bid.0).is_some())
+            .collect();
+
+        Ok(AuctionStatus {
+            leader: winners.first().cloned(),
+            winners,
+            ended: auction.ended(now)?,
+            minimum_price: match auction.price_floor {
+                PriceFloor::None(_) => Some(0),
+                PriceFloor::MinimumPrice(min) => Some(min[0]),
+                PriceFloor::BlindedPrice(_) => None,
+            },
+        })
+    }
+}
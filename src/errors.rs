@@ -134,6 +134,134 @@ pub enum AuctionError {
     /// Gap tick size percentage must be between 0 and 100
     #[error("Gap tick size percentage must be between 0 and 100")]
     InvalidGapTickSizePercentage,
+
+    /// Dutch auctions need an end time, a single winner, no tick sizes and a start price at or above the floor price
+    #[error("Dutch auctions need an end time, a single winner, no tick sizes and a start price at or above the floor price")]
+    InvalidDutchAuctionConfig,
+
+    /// Sealed-bid auctions need an end time, a reveal window, capped winners and no gap tick size or instant sale price
+    #[error("Sealed-bid auctions need an end time, a reveal window, capped winners and no gap tick size or instant sale price")]
+    InvalidSealedBidConfig,
+
+    /// Bids on a sealed-bid auction must be committed and then revealed
+    #[error("Bids on a sealed-bid auction must be committed and then revealed")]
+    BidMustBeSealed,
+
+    /// This auction does not take sealed bids
+    #[error("This auction does not take sealed bids")]
+    NotSealedBidAuction,
+
+    /// Sealed bids cannot be committed, revealed or withdrawn at this point of the auction
+    #[error("Sealed bids cannot be committed, revealed or withdrawn at this point of the auction")]
+    InvalidSealedBidPhase,
+
+    /// Sealed bid has already been revealed
+    #[error("Sealed bid has already been revealed")]
+    BidAlreadyRevealed,
+
+    /// Uniform price auctions need capped winners and no instant sale price
+    #[error("Uniform price auctions need capped winners and no instant sale price")]
+    InvalidUniformPriceConfig,
+
+    /// Winners of this auction pay their own bid, there is no excess to refund
+    #[error("Winners of this auction pay their own bid, there is no excess to refund")]
+    NoClearingPrice,
+
+    /// Auction is scheduled to start at a later time
+    #[error("Auction is scheduled to start at a later time")]
+    AuctionNotYetStarted,
+
+    /// Auction has no scheduled start time
+    #[error("Auction has no scheduled start time")]
+    AuctionNotScheduled,
+
+    /// Anti-sniping needs an end time, a positive window and extension, and bids placed in the open
+    #[error("Anti-sniping needs an end time, a positive window and extension, and bids placed in the open")]
+    InvalidAntiSnipingPolicy,
+
+    /// Auction cannot be closed while bidder pots still hold funds
+    #[error("Auction cannot be closed while bidder pots still hold funds")]
+    BidderPotsNotDrained,
+
+    /// Auction does not record the payer its rent should be returned to
+    #[error("Auction does not record the payer its rent should be returned to")]
+    InvalidPayer,
+
+    /// Token close failed
+    #[error("Token close failed")]
+    TokenCloseFailed,
+
+    /// Bidder accounts can only be closed once the bid is settled
+    #[error("Bidder accounts can only be closed once the bid is settled")]
+    BidderAccountsNotSettled,
+
+    /// Account already uses the current layout
+    #[error("Account already uses the current layout")]
+    AccountAlreadyMigrated,
+
+    /// Account does not use a layout that can be migrated
+    #[error("Account does not use a layout that can be migrated")]
+    InvalidMigration,
+
+    /// Native SOL bidding is not supported for this auction type
+    #[error("Native SOL bidding is not supported for this auction type")]
+    NativeMintNotSupported,
+
+    /// Fee splits need one primary recipient and must add up to 10,000 basis points
+    #[error("Fee splits need one primary recipient and must add up to 10,000 basis points")]
+    InvalidFeeSplits,
+
+    /// Fee split recipients must be given in the order the auction stores them
+    #[error("Fee split recipients must be given in the order the auction stores them")]
+    FeeSplitRecipientMismatch,
+
+    /// Bidder is not on the auction allowlist
+    #[error("Bidder is not on the auction allowlist")]
+    BidderNotAllowed,
+
+    /// Bid limits need at least one bid per bidder and an auction with several winners
+    #[error("Bid limits need at least one bid per bidder and an auction with several winners")]
+    InvalidBidLimit,
+
+    /// Bidder already holds as many bids as the auction allows
+    #[error("Bidder already holds as many bids as the auction allows")]
+    BidLimitReached,
+
+    /// Only the wallet that placed the bid can act on it
+    #[error("Only the wallet that placed the bid can act on it")]
+    InvalidBidOwner,
+
+    /// Bids on this auction cannot be increased
+    #[error("Bids on this auction cannot be increased")]
+    BidCannotBeIncreased,
+
+    /// Auction is paused
+    #[error("Auction is paused")]
+    AuctionPaused,
+
+    /// No authority has been proposed for this auction
+    #[error("No authority has been proposed for this auction")]
+    NoPendingAuthority,
+
+    /// Auction was cancelled, nobody won it
+    #[error("Auction was cancelled, nobody won it")]
+    AuctionCancelled,
+
+    /// Crank reward is above the largest share allowed
+    #[error("Crank reward is above the largest share allowed")]
+    InvalidCrankReward,
+
+    /// Winner limit is below the bids already winning the auction
+    #[error("Winner limit is below the bids already winning the auction")]
+    InvalidWinnerLimit,
+
+    /// Auction cannot be closed while bidder accounts are still open
+    #[error("Auction cannot be closed while bidder accounts are still open")]
+    BidderAccountsNotClosed,
+
+    /// Auction account can only grow by 10KB in one instruction
+    #[error("Auction account can only grow by 10KB in one instruction")]
+    AuctionGrowthTooLarge,
 }
 
 impl PrintProgramError for AuctionError {
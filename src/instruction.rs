@@ -1,4 +1,4 @@
-use crate::{EXTENDED, PREFIX};
+use crate::{processor::bid_key, EXTENDED, FEE_SPLITS, PREFIX};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -7,9 +7,20 @@ use solana_program::{
 };
 
 pub use crate::processor::{
-    cancel_bid::CancelBidArgs, claim_bid::ClaimBidArgs, create_auction::CreateAuctionArgs,
-    create_auction_v2::CreateAuctionArgsV2, end_auction::EndAuctionArgs, place_bid::PlaceBidArgs,
-    start_auction::StartAuctionArgs,
+    accept_authority::AcceptAuthorityArgs, cancel_auction::CancelAuctionArgs,
+    cancel_bid::CancelBidArgs, claim_bid::ClaimBidArgs, claim_bids::ClaimBidsArgs,
+    close_auction::CloseAuctionArgs, close_bidder_accounts::CloseBidderAccountsArgs,
+    commit_bid::CommitBidArgs, create_auction::CreateAuctionArgs,
+    create_auction_v2::CreateAuctionArgsV2, create_auction_v3::CreateAuctionArgsV3,
+    create_auction_v4::CreateAuctionArgsV4, create_auction_v5::CreateAuctionArgsV5,
+    create_auction_v6::CreateAuctionArgsV6, end_auction::EndAuctionArgs,
+    increase_bid::IncreaseBidArgs, migrate_account::MigrateAccountArgs,
+    pause_auction::PauseAuctionArgs, place_bid::PlaceBidArgs, place_bid_v2::PlaceBidArgsV2,
+    place_bid_v3::PlaceBidArgsV3, propose_authority::ProposeAuthorityArgs,
+    refund_bids::RefundBidsArgs, refund_excess::RefundExcessArgs,
+    resize_auction::ResizeAuctionArgs, reveal_bid::RevealBidArgs,
+    set_authority_roles::SetAuthorityRolesArgs, set_crank_reward::SetCrankRewardArgs,
+    start_auction::StartAuctionArgs, update_auction::UpdateAuctionArgs,
 };
 
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
@@ -21,11 +32,19 @@ pub enum AuctionInstruction {
     ///   3. `[writable]` The pot SPL account, where the tokens will be deposited.
     ///   4. `[writable]` The metadata account, storing information about the bidders actions.
     ///   5. `[writable]` Auction account, containing data about the auction and item being bid on.
-    ///   6. `[writable]` Token mint, for transfer instructions and verification.
-    ///   7. `[]` Clock sysvar
-    ///   8. `[]` Rent sysvar
-    ///   9. `[]` System program
-    ///   10. `[]` SPL Token Program
+    ///   6. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   7. `[writable]` Token mint, for transfer instructions and verification.
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Rent sysvar
+    ///   10. `[]` System program
+    ///   11. `[]` SPL Token Program
+    ///   12. `[writable]` The bidder commitment account, required to withdraw from a sealed-bid auction
+    ///           [seed of ['auction', program_id, auction key, bidder key, 'commitment']]
+    ///   13. `[signer]` The wallet that placed the bid, required when the bidder is a bid key that
+    ///           cannot sign, see PlaceBidV3.
+    ///
+    /// On native SOL auctions the pot itself stands in for the pot SPL account and the refund is
+    /// paid in lamports to the bidders token account, which can be any account.
     CancelBid(CancelBidArgs),
 
     /// Create a new auction account bound to a resource, initially in a pending state.
@@ -40,29 +59,43 @@ pub enum AuctionInstruction {
     ///   0. `[writable]` The destination account
     ///   1. `[writable]` The bidder pot token account
     ///   2. `[]` The bidder pot pda account [seed of ['auction', program_id, auction key, bidder key]]
-    ///   3. `[signer]` The authority on the auction
+    ///   3. `[signer]` The authority on the auction, or the claimer it delegated to
     ///   4. `[]` The auction
     ///   5. `[]` The bidder wallet
     ///   6. `[]` Token mint of the auction
     ///   7. `[]` Clock sysvar
     ///   8. `[]` Token program
-    ///   9. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   9. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   10. `[]` The bidder commitment account, required to claim the deposit of a sealed bid that was
+    ///           never revealed [seed of ['auction', program_id, auction key, bidder key, 'commitment']]
+    ///   11. `[]` The bidder metadata account, required on sealed-bid and uniform price auctions
+    ///           [seed of ['auction', program_id, auction key, bidder key, 'metadata']]
+    ///   12. `[]` The fee splits, required on auctions created with them
+    ///           [seed of ['auction', program_id, resource key, 'splits']]
+    ///   13..13+n. `[writable]` One account per fee split recipient, in the stored order.
+    ///
+    /// On native SOL auctions the pot itself stands in for the pot SPL account and the winnings are
+    /// paid in lamports to the destination, which can be any account. On auctions with fee splits
+    /// the winnings go to the recipients instead of the destination.
     ClaimBid(ClaimBidArgs),
 
     /// Ends an auction, regardless of end timing conditions
     ///
-    ///   0. `[writable, signer]` Auction authority
+    ///   0. `[writable, signer]` Auction authority, or the starter it delegated to
     ///   1. `[writable]` Auction
     ///   6. `[]` Clock sysvar
+    ///   3. `[]` Auction extended, required when the starter signs (pda relative to auction of ['auction', program id, vault key, 'extended'])
     EndAuction(EndAuctionArgs),
 
     /// Start an inactive auction.
-    ///   0. `[signer]` The creator/authorised account.
+    ///   0. `[signer]` The creator/authorised account, or the starter it delegated to.
     ///   1. `[writable]` Initialized auction account.
     ///   2. `[]` Clock sysvar
+    ///   3. `[]` Auction extended, required when the starter signs or the start is scheduled (pda relative to auction of ['auction', program id, vault key, 'extended'])
     StartAuction(StartAuctionArgs),
 
-    /// Update the authority for an auction account.
+    /// Update the authority for an auction account. Prefer ProposeAuthority, which cannot hand the
+    /// auction to a key nobody holds.
     ///   0. `[writable]` auction (pda of ['auction', program id, resource id])
     ///   1. `[signer]` authority
     ///   2. `[]` newAuthority
@@ -82,6 +115,10 @@ pub enum AuctionInstruction {
     ///   10. `[]` Rent sysvar
     ///   11. `[]` System program
     ///   12. `[]` SPL Token Program
+    ///
+    /// On native SOL auctions, created with the native mint as token mint, the bidders token account
+    /// is the wallet paying the bid in lamports and must sign, and the pot itself stands in for the
+    /// pot SPL account.
     PlaceBid(PlaceBidArgs),
 
     /// Create a new auction account bound to a resource, initially in a pending state.
@@ -92,6 +129,263 @@ pub enum AuctionInstruction {
     ///   3. `[]` Rent sysvar
     ///   4. `[]` System account
     CreateAuctionV2(CreateAuctionArgsV2),
+
+    /// Commit a sealed bid on a running sealed-bid auction.
+    ///   0. `[signer]` The bidders primary account, for PDA calculation/transit auth.
+    ///   1. `[writable]` The bidders token account they'll pay with
+    ///   2. `[writable]` The pot, containing a reference to the stored SPL token account.
+    ///   3. `[writable]` The pot SPL account, where the tokens will be deposited.
+    ///   4. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   5. `[writable]` The commitment account [seed of ['auction', program_id, auction key, bidder key, 'commitment']]
+    ///   6. `[writable]` Auction account, containing data about the auction and item being bid on.
+    ///   7. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   8. `[writable]` Token mint, for transfer instructions and verification.
+    ///   9. `[signer]` Transfer authority, for moving tokens into the bid pot.
+    ///   10. `[signer]` Payer
+    ///   11. `[]` Clock sysvar
+    ///   12. `[]` Rent sysvar
+    ///   13. `[]` System program
+    ///   14. `[]` SPL Token Program
+    CommitBid(CommitBidArgs),
+
+    /// Reveal a sealed bid once the commit phase of a sealed-bid auction is over.
+    ///   0. `[signer]` The bidders primary account.
+    ///   1. `[]` The pot, containing a reference to the stored SPL token account.
+    ///   2. `[]` The pot SPL account, which must hold at least the revealed amount.
+    ///   3. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   4. `[writable]` The commitment account [seed of ['auction', program_id, auction key, bidder key, 'commitment']]
+    ///   5. `[writable]` Auction account, containing data about the auction and item being bid on.
+    ///   6. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   7. `[]` Clock sysvar
+    RevealBid(RevealBidArgs),
+
+    /// Refund a winning bidder what their pot holds above the clearing price, for auctions where
+    /// every winner pays the same price.
+    ///   0. `[signer]` The bidders primary account.
+    ///   1. `[writable]` The bidders token account they'll receive the refund with
+    ///   2. `[]` The pot, containing a reference to the stored SPL token account.
+    ///   3. `[writable]` The pot SPL account, where the tokens will be refunded from.
+    ///   4. `[]` Auction account, containing data about the auction and item being bid on.
+    ///   5. `[]` Token mint, for transfer instructions and verification.
+    ///   6. `[]` Clock sysvar
+    ///   7. `[]` SPL Token Program
+    ///   8. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   9. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   10. `[signer]` The wallet that placed the bid, required when the bidder is a bid key that
+    ///           cannot sign, see PlaceBidV3.
+    RefundExcess(RefundExcessArgs),
+
+    /// Create a new auction account bound to a resource, initially in a pending state.
+    /// The only differences with CreateAuctionV2 are the scheduled start time and the anti-sniping
+    /// policy in CreateAuctionArgsV3
+    ///   0. `[signer]` The account creating the auction, which is authorised to make changes.
+    ///   1. `[writable]` Uninitialized auction account.
+    ///   2. `[writable]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   3. `[]` Rent sysvar
+    ///   4. `[]` System account
+    CreateAuctionV3(CreateAuctionArgsV3),
+
+    /// Start an auction whose scheduled start time has passed, anyone may send this.
+    ///   0. `[writable]` Initialized auction account.
+    ///   1. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   2. `[]` Clock sysvar
+    StartScheduledAuction(StartAuctionArgs),
+
+    /// Close an ended auction once every bid is settled and its accounts closed with
+    /// CloseBidderAccounts, returning its rent to the payer recorded at creation, anyone may send this.
+    ///   0. `[writable]` Auction account.
+    ///   1. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   2. `[writable]` The account that paid for the auction, receiving the rent.
+    ///   3. `[]` Clock sysvar
+    ///   4. `[writable]` The fee splits, required on auctions created with them
+    ///           [seed of ['auction', program_id, resource key, 'splits']]
+    CloseAuction(CloseAuctionArgs),
+
+    /// Close the accounts of a settled bid, returning their rent to the payer recorded in the pot.
+    ///   0. `[signer]` The bidder, or the auction authority.
+    ///   1. `[]` The bidders primary account, for PDA calculation.
+    ///   2. `[writable]` The pot, containing a reference to the stored SPL token account.
+    ///   3. `[writable]` The pot SPL account, which must be empty.
+    ///   4. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   5. `[]` Auction account, containing data about the auction and item being bid on.
+    ///   6. `[writable]` The account that paid for the bid accounts, receiving the rent.
+    ///   7. `[]` SPL Token Program
+    ///   8. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   9. `[writable]` Optional bidder commitment account of a sealed bid, closed as well
+    ///           [seed of ['auction', program_id, auction key, bidder key, 'commitment']]
+    CloseBidderAccounts(CloseBidderAccountsArgs),
+
+    /// Upgrade an account created before accounts carried a leading Key to the current layout.
+    ///   0. `[writable]` The account being migrated, derived from the resource and bidder in args.
+    ///   1. `[signer, writable]` Payer, covering the rent of the extra space.
+    ///   2. `[]` Rent sysvar
+    ///   3. `[]` System program
+    MigrateAccount(MigrateAccountArgs),
+
+    /// Create a new auction account bound to a resource, initially in a pending state.
+    /// The only one difference with CreateAuctionV3 is the fee splits in CreateAuctionArgsV4
+    ///   0. `[signer]` The account creating the auction, which is authorised to make changes.
+    ///   1. `[writable]` Uninitialized auction account.
+    ///   2. `[writable]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   3. `[]` Rent sysvar
+    ///   4. `[]` System account
+    ///   5. `[writable]` Uninitialized fee splits account, required when fee splits are given
+    ///           (pda relative to auction of ['auction', program id, vault key, 'splits']).
+    CreateAuctionV4(CreateAuctionArgsV4),
+
+    /// Create a new auction account bound to a resource, initially in a pending state.
+    /// The only one difference with CreateAuctionV4 is the bidder allowlist in CreateAuctionArgsV5
+    ///   0. `[signer]` The account creating the auction, which is authorised to make changes.
+    ///   1. `[writable]` Uninitialized auction account.
+    ///   2. `[writable]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   3. `[]` Rent sysvar
+    ///   4. `[]` System account
+    ///   5. `[writable]` Uninitialized fee splits account, required when fee splits are given
+    ///           (pda relative to auction of ['auction', program id, vault key, 'splits']).
+    CreateAuctionV5(CreateAuctionArgsV5),
+
+    /// Place a bid on a running auction, proving the bidder is on the auction allowlist.
+    /// Takes the same accounts as PlaceBid.
+    PlaceBidV2(PlaceBidArgsV2),
+
+    /// Create a new auction account bound to a resource, initially in a pending state.
+    /// The only one difference with CreateAuctionV5 is the bid limit per bidder in CreateAuctionArgsV6
+    ///   0. `[signer]` The account creating the auction, which is authorised to make changes.
+    ///   1. `[writable]` Uninitialized auction account.
+    ///   2. `[writable]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   3. `[]` Rent sysvar
+    ///   4. `[]` System account
+    ///   5. `[writable]` Uninitialized fee splits account, required when fee splits are given
+    ///           (pda relative to auction of ['auction', program id, vault key, 'splits']).
+    CreateAuctionV6(CreateAuctionArgsV6),
+
+    /// Place one of several bids a bidder may hold on a running auction. Takes the same accounts
+    /// as PlaceBid, with the pot and metadata derived from the bid key in place of the bidder, the
+    /// bidder itself for the first bid and
+    /// ['auction', program_id, auction key, bidder key, 'bid', bid index] for any others.
+    PlaceBidV3(PlaceBidArgsV3),
+
+    /// Raise a bid on a running English or uniform price auction, paying only the difference.
+    ///   0. `[signer]` The bidders primary account, or bid key of a further bid, see PlaceBidV3.
+    ///   1. `[writable]` The bidders token account they'll pay with
+    ///   2. `[]` The pot, containing a reference to the stored SPL token account.
+    ///   3. `[writable]` The pot SPL account, where the tokens will be deposited.
+    ///   4. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   5. `[writable]` Auction account, containing data about the auction and item being bid on.
+    ///   6. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   7. `[]` Token mint, for transfer instructions and verification.
+    ///   8. `[signer]` Transfer authority, for moving tokens into the bid pot.
+    ///   9. `[]` Clock sysvar
+    ///   10. `[]` System program
+    ///   11. `[]` SPL Token Program
+    ///   12. `[signer]` The wallet that placed the bid, required when the bidder is a bid key that
+    ///           cannot sign, see PlaceBidV3.
+    ///
+    /// On native SOL auctions the bidders token account is the wallet paying the difference in
+    /// lamports and must sign, and the pot itself stands in for the pot SPL account.
+    IncreaseBid(IncreaseBidArgs),
+
+    /// Rewrite the settings of an auction that has not started, or push back the end time of a
+    /// started auction nobody has bid on yet.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[writable]` Auction account.
+    ///   2. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   3. `[]` Clock sysvar
+    UpdateAuction(UpdateAuctionArgs),
+
+    /// Pause a running auction. No bids are taken until it resumes, cancelling still works.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[writable]` Auction account.
+    ///   2. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   3. `[]` Clock sysvar
+    PauseAuction(PauseAuctionArgs),
+
+    /// Resume a paused auction, its end time is pushed back by as long as it was paused.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[writable]` Auction account.
+    ///   2. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   3. `[]` Clock sysvar
+    ResumeAuction(PauseAuctionArgs),
+
+    /// Propose a new authority for an auction, which takes over once it accepts.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[]` Auction account.
+    ///   2. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ProposeAuthority(ProposeAuthorityArgs),
+
+    /// Take over an auction as the proposed authority.
+    ///   0. `[signer]` The proposed authority
+    ///   1. `[writable]` Auction account.
+    ///   2. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    AcceptAuthority(AcceptAuthorityArgs),
+
+    /// Delegate the claimer and starter roles of an auction.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[]` Auction account.
+    ///   2. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    SetAuthorityRoles(SetAuthorityRolesArgs),
+
+    /// Cancel an auction that is not over, nobody wins it and every bidder can take their bid back.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[writable]` Auction account.
+    ///   2. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   3. `[]` Clock sysvar
+    CancelAuction(CancelAuctionArgs),
+
+    /// Set the share of each losing bid paid to the sender of RefundBids, before the auction starts.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[]` Auction account.
+    ///   2. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   3. `[]` Clock sysvar
+    SetCrankReward(SetCrankRewardArgs),
+
+    /// Refund losing bids of an auction that is over, anyone can send this.
+    ///   0. `[signer]` The sender
+    ///   1. `[writable]` Token account of the sender taking the crank reward, the sender itself on
+    ///           native SOL auctions
+    ///   2. `[]` Auction account.
+    ///   3. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   4. `[]` Token mint of the auction
+    ///   5. `[]` Clock sysvar
+    ///   6. `[]` Token program
+    ///   7..7+5n. For each bid, in order:
+    ///           `[]` The bidder
+    ///           `[writable]` Token account of the wallet that placed the bid, the wallet itself on
+    ///               native SOL auctions
+    ///           `[writable]` The bidder pot pda account [seed of ['auction', program_id, auction key, bidder key]]
+    ///           `[writable]` The bidder pot token account, the pot itself on native SOL auctions
+    ///           `[writable]` The bidder metadata [seed of ['auction', program_id, auction key, bidder key, 'metadata']]
+    RefundBids(RefundBidsArgs),
+
+    /// Move the winnings of many bids of an English auction to the destination account at once.
+    ///   0. `[writable]` The destination account
+    ///   1. `[signer]` The authority on the auction, or the claimer it delegated to
+    ///   2. `[]` The auction
+    ///   3. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   4. `[]` Token mint of the auction
+    ///   5. `[]` Clock sysvar
+    ///   6. `[]` Token program
+    ///   7. `[]` The fee splits, only read on auctions created with them
+    ///           [seed of ['auction', program_id, resource key, 'splits']]
+    ///   8..8+n. `[writable]` One account per fee split recipient, in the stored order.
+    ///   8+n..8+n+3m. For each winner, in any order:
+    ///           `[]` The bidder
+    ///           `[writable]` The bidder pot pda account [seed of ['auction', program_id, auction key, bidder key]]
+    ///           `[writable]` The bidder pot token account, the pot itself on native SOL auctions
+    ///
+    /// On auctions with fee splits the winnings go to the recipients instead of the destination.
+    ClaimBids(ClaimBidsArgs),
+
+    /// Raise the winners of a capped auction, or the bids it holds, while it is taking bids. The
+    /// account grows by at most 10KB per instruction, larger raises are sent in steps.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[writable]` Auction account.
+    ///   2. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   3. `[signer, writable]` Payer, tops up rent as the auction account grows
+    ///   4. `[]` Rent sysvar
+    ///   5. `[]` System program
+    ///   6. `[]` Clock sysvar
+    ResizeAuction(ResizeAuctionArgs),
 }
 
 /// Creates an CreateAuction instruction.
@@ -166,6 +460,177 @@ pub fn create_auction_instruction_v2(
     }
 }
 
+/// Creates an CreateAuctionV3 instruction.
+pub fn create_auction_instruction_v3(
+    program_id: Pubkey,
+    creator_pubkey: Pubkey,
+    args: CreateAuctionArgsV3,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: AuctionInstruction::CreateAuctionV3(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CreateAuctionV4 instruction.
+pub fn create_auction_instruction_v4(
+    program_id: Pubkey,
+    creator_pubkey: Pubkey,
+    args: CreateAuctionArgsV4,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        FEE_SPLITS.as_bytes(),
+    ];
+    let (fee_splits_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(fee_splits_pubkey, false),
+        ],
+        data: AuctionInstruction::CreateAuctionV4(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CreateAuctionV5 instruction.
+pub fn create_auction_instruction_v5(
+    program_id: Pubkey,
+    creator_pubkey: Pubkey,
+    args: CreateAuctionArgsV5,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        FEE_SPLITS.as_bytes(),
+    ];
+    let (fee_splits_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(fee_splits_pubkey, false),
+        ],
+        data: AuctionInstruction::CreateAuctionV5(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CreateAuctionV6 instruction.
+pub fn create_auction_instruction_v6(
+    program_id: Pubkey,
+    creator_pubkey: Pubkey,
+    args: CreateAuctionArgsV6,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        FEE_SPLITS.as_bytes(),
+    ];
+    let (fee_splits_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(fee_splits_pubkey, false),
+        ],
+        data: AuctionInstruction::CreateAuctionV6(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 /// Creates an SetAuthority instruction.
 pub fn set_authority_instruction(
     program_id: Pubkey,
@@ -200,12 +665,21 @@ pub fn start_auction_instruction(
     ];
     let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
 
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
     Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(authority_pubkey, true),
             AccountMeta::new(auction_pubkey, false),
             AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
         ],
         data: AuctionInstruction::StartAuction(args).try_to_vec().unwrap(),
     }
@@ -279,16 +753,184 @@ pub fn place_bid_instruction(
     }
 }
 
-/// Creates an CancelBidinstruction.
-pub fn cancel_bid_instruction(
+/// Creates a PlaceBidV2 instruction.
+pub fn place_bid_instruction_v2(
     program_id: Pubkey,
     bidder_pubkey: Pubkey,
     bidder_token_pubkey: Pubkey,
     bidder_pot_token_pubkey: Pubkey,
     token_mint_pubkey: Pubkey,
-    args: CancelBidArgs,
+    transfer_authority: Pubkey,
+    payer: Pubkey,
+    args: PlaceBidArgsV2,
+) -> Instruction {
+    let mut instruction = place_bid_instruction(
+        program_id,
+        bidder_pubkey,
+        bidder_token_pubkey,
+        bidder_pot_token_pubkey,
+        token_mint_pubkey,
+        transfer_authority,
+        payer,
+        PlaceBidArgs {
+            amount: args.amount,
+            resource: args.resource,
+        },
+    );
+    instruction.data = AuctionInstruction::PlaceBidV2(args).try_to_vec().unwrap();
+    instruction
+}
+
+/// Creates a PlaceBidV3 instruction.
+pub fn place_bid_instruction_v3(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    transfer_authority: Pubkey,
+    payer: Pubkey,
+    args: PlaceBidArgsV3,
 ) -> Instruction {
-    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+    let bid_pubkey = bid_key(&program_id, &auction_pubkey, &bidder_pubkey, args.bid_index);
+
+    // Derive the pot and metadata from the bid key, the bidder still signs.
+    let mut instruction = place_bid_instruction(
+        program_id,
+        bid_pubkey,
+        bidder_token_pubkey,
+        bidder_pot_token_pubkey,
+        token_mint_pubkey,
+        transfer_authority,
+        payer,
+        PlaceBidArgs {
+            amount: args.amount,
+            resource: args.resource,
+        },
+    );
+    instruction.accounts[0] = AccountMeta::new(bidder_pubkey, true);
+    instruction.data = AuctionInstruction::PlaceBidV3(args).try_to_vec().unwrap();
+    instruction
+}
+
+/// Creates an IncreaseBid instruction.
+pub fn increase_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    transfer_authority: Pubkey,
+    args: IncreaseBidArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(bidder_pubkey, true),
+            AccountMeta::new(bidder_token_pubkey, false),
+            AccountMeta::new_readonly(bidder_pot_pubkey, false),
+            AccountMeta::new(bidder_pot_token_pubkey, false),
+            AccountMeta::new(bidder_meta_pubkey, false),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(token_mint_pubkey, false),
+            AccountMeta::new_readonly(transfer_authority, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: AuctionInstruction::IncreaseBid(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates a PlaceBid instruction for a native SOL auction, paying the bid in lamports from the
+/// bidders wallet.
+pub fn place_native_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    payer: Pubkey,
+    args: PlaceBidArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    place_bid_instruction(
+        program_id,
+        bidder_pubkey,
+        bidder_pubkey,
+        bidder_pot_pubkey,
+        spl_token::native_mint::id(),
+        bidder_pubkey,
+        payer,
+        args,
+    )
+}
+
+/// Creates an CancelBidinstruction.
+pub fn cancel_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    args: CancelBidArgs,
+) -> Instruction {
+    // Derive Auction Key
     let seeds = &[
         PREFIX.as_bytes(),
         program_id.as_ref(),
@@ -323,6 +965,16 @@ pub fn cancel_bid_instruction(
     ];
     let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
 
+    // Derive Bidder Commitment, only read for sealed-bid auctions
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "commitment".as_bytes(),
+    ];
+    let (bidder_commitment_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
     Instruction {
         program_id,
         accounts: vec![
@@ -338,6 +990,7 @@ pub fn cancel_bid_instruction(
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(bidder_commitment_pubkey, false),
         ],
         data: AuctionInstruction::CancelBid(args).try_to_vec().unwrap(),
     }
@@ -356,12 +1009,21 @@ pub fn end_auction_instruction(
     ];
     let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
 
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
     Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(authority_pubkey, true),
             AccountMeta::new(auction_pubkey, false),
             AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
         ],
         data: AuctionInstruction::EndAuction(args).try_to_vec().unwrap(),
     }
@@ -374,6 +1036,7 @@ pub fn claim_bid_instruction(
     bidder_pubkey: Pubkey,
     bidder_pot_token_pubkey: Pubkey,
     token_mint_pubkey: Pubkey,
+    token_program_pubkey: Pubkey,
     auction_extended_pubkey: Option<Pubkey>,
     args: ClaimBidArgs,
 ) -> Instruction {
@@ -403,12 +1066,42 @@ pub fn claim_bid_instruction(
         AccountMeta::new_readonly(bidder_pubkey, false),
         AccountMeta::new_readonly(token_mint_pubkey, false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
-        AccountMeta::new_readonly(spl_token::id(), false),        
+        AccountMeta::new_readonly(token_program_pubkey, false),        
     ];
 
-    if let Some(auction_extended) = auction_extended_pubkey {
-        accounts.push(AccountMeta::new_readonly(auction_extended, false));
-    }
+    // Derive Auction Extended Key when it is not given
+    let auction_extended = auction_extended_pubkey.unwrap_or_else(|| {
+        let seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, &program_id).0
+    });
+    accounts.push(AccountMeta::new(auction_extended, false));
+
+    // Derive Bidder Commitment, only read for sealed-bid auctions
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "commitment".as_bytes(),
+    ];
+    let (bidder_commitment_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+    accounts.push(AccountMeta::new_readonly(bidder_commitment_pubkey, false));
+
+    // Derive Bidder Metadata
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_metadata_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+    accounts.push(AccountMeta::new_readonly(bidder_metadata_pubkey, false));
 
     Instruction {
         program_id,
@@ -416,3 +1109,919 @@ pub fn claim_bid_instruction(
         data: AuctionInstruction::ClaimBid(args).try_to_vec().unwrap(),
     }
 }
+
+/// Creates a ClaimBid instruction for an auction created with fee splits, the recipients must be
+/// given in the order the auction stores them.
+pub fn claim_bid_with_fee_splits_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    token_program_pubkey: Pubkey,
+    recipients: Vec<Pubkey>,
+    args: ClaimBidArgs,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        FEE_SPLITS.as_bytes(),
+    ];
+    let (fee_splits_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // The destination is not paid on these auctions, the first recipient fills its place.
+    let destination_pubkey = recipients.first().cloned().unwrap_or_default();
+    let mut instruction = claim_bid_instruction(
+        program_id,
+        destination_pubkey,
+        authority_pubkey,
+        bidder_pubkey,
+        bidder_pot_token_pubkey,
+        token_mint_pubkey,
+        token_program_pubkey,
+        None,
+        args,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(fee_splits_pubkey, false));
+    for recipient in recipients {
+        instruction
+            .accounts
+            .push(AccountMeta::new(recipient, false));
+    }
+    instruction
+}
+
+/// Creates an CommitBid instruction.
+pub fn commit_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    transfer_authority: Pubkey,
+    payer: Pubkey,
+    args: CommitBidArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Commitment
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "commitment".as_bytes(),
+    ];
+    let (bidder_commitment_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(bidder_pubkey, true),
+            AccountMeta::new(bidder_token_pubkey, false),
+            AccountMeta::new(bidder_pot_pubkey, false),
+            AccountMeta::new(bidder_pot_token_pubkey, false),
+            AccountMeta::new(bidder_meta_pubkey, false),
+            AccountMeta::new(bidder_commitment_pubkey, false),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new(token_mint_pubkey, false),
+            AccountMeta::new_readonly(transfer_authority, true),
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: AuctionInstruction::CommitBid(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an RevealBid instruction.
+pub fn reveal_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    args: RevealBidArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Commitment
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "commitment".as_bytes(),
+    ];
+    let (bidder_commitment_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(bidder_pubkey, true),
+            AccountMeta::new_readonly(bidder_pot_pubkey, false),
+            AccountMeta::new_readonly(bidder_pot_token_pubkey, false),
+            AccountMeta::new(bidder_meta_pubkey, false),
+            AccountMeta::new(bidder_commitment_pubkey, false),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::RevealBid(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an RefundExcess instruction.
+pub fn refund_excess_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    args: RefundExcessArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Metadata key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(bidder_pubkey, true),
+            AccountMeta::new(bidder_token_pubkey, false),
+            AccountMeta::new_readonly(bidder_pot_pubkey, false),
+            AccountMeta::new(bidder_pot_token_pubkey, false),
+            AccountMeta::new_readonly(auction_pubkey, false),
+            AccountMeta::new_readonly(token_mint_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(bidder_meta_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+        ],
+        data: AuctionInstruction::RefundExcess(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an StartScheduledAuction instruction.
+pub fn start_scheduled_auction_instruction(
+    program_id: Pubkey,
+    args: StartAuctionArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::StartScheduledAuction(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an UpdateAuction instruction.
+pub fn update_auction_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: UpdateAuctionArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::UpdateAuction(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an PauseAuction instruction.
+pub fn pause_auction_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: PauseAuctionArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::PauseAuction(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an ResumeAuction instruction.
+pub fn resume_auction_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: PauseAuctionArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::ResumeAuction(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an ProposeAuthority instruction.
+pub fn propose_authority_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: ProposeAuthorityArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new_readonly(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+        ],
+        data: AuctionInstruction::ProposeAuthority(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an AcceptAuthority instruction.
+pub fn accept_authority_instruction(
+    program_id: Pubkey,
+    new_authority_pubkey: Pubkey,
+    args: AcceptAuthorityArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(new_authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+        ],
+        data: AuctionInstruction::AcceptAuthority(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetAuthorityRoles instruction.
+pub fn set_authority_roles_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: SetAuthorityRolesArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new_readonly(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+        ],
+        data: AuctionInstruction::SetAuthorityRoles(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CancelAuction instruction.
+pub fn cancel_auction_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: CancelAuctionArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::CancelAuction(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetCrankReward instruction.
+pub fn set_crank_reward_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: SetCrankRewardArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new_readonly(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::SetCrankReward(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an RefundBids instruction. Each bid is given as the bidder, the account taking the
+/// refund and the bidder pot token account, the pot itself on native SOL auctions.
+pub fn refund_bids_instruction(
+    program_id: Pubkey,
+    cranker_pubkey: Pubkey,
+    cranker_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    bids: Vec<(Pubkey, Pubkey, Pubkey)>,
+    args: RefundBidsArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(cranker_pubkey, true),
+        AccountMeta::new(cranker_token_pubkey, false),
+        AccountMeta::new_readonly(auction_pubkey, false),
+        AccountMeta::new(auction_extended_pubkey, false),
+        AccountMeta::new_readonly(token_mint_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    for (bidder_pubkey, bidder_token_pubkey, bidder_pot_token_pubkey) in bids {
+        // Derive Bidder Pot
+        let seeds = &[
+            PREFIX.as_bytes(),
+            &program_id.as_ref(),
+            auction_pubkey.as_ref(),
+            bidder_pubkey.as_ref(),
+        ];
+        let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+        // Derive Bidder Meta
+        let seeds = &[
+            PREFIX.as_bytes(),
+            &program_id.as_ref(),
+            auction_pubkey.as_ref(),
+            bidder_pubkey.as_ref(),
+            "metadata".as_bytes(),
+        ];
+        let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+        accounts.push(AccountMeta::new_readonly(bidder_pubkey, false));
+        accounts.push(AccountMeta::new(bidder_token_pubkey, false));
+        accounts.push(AccountMeta::new(bidder_pot_pubkey, false));
+        accounts.push(AccountMeta::new(bidder_pot_token_pubkey, false));
+        accounts.push(AccountMeta::new(bidder_meta_pubkey, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: AuctionInstruction::RefundBids(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an ClaimBids instruction. Each winner is given as the bidder and the bidder pot token
+/// account, the pot itself on native SOL auctions. Recipients are only needed on auctions with fee
+/// splits, in the order the splits store them.
+pub fn claim_bids_instruction(
+    program_id: Pubkey,
+    destination_pubkey: Pubkey,
+    authority_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    recipients: Vec<Pubkey>,
+    winners: Vec<(Pubkey, Pubkey)>,
+    args: ClaimBidsArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        FEE_SPLITS.as_bytes(),
+    ];
+    let (fee_splits_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(destination_pubkey, false),
+        AccountMeta::new_readonly(authority_pubkey, true),
+        AccountMeta::new_readonly(auction_pubkey, false),
+        AccountMeta::new(auction_extended_pubkey, false),
+        AccountMeta::new_readonly(token_mint_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(fee_splits_pubkey, false),
+    ];
+    for recipient in recipients {
+        accounts.push(AccountMeta::new(recipient, false));
+    }
+
+    for (bidder_pubkey, bidder_pot_token_pubkey) in winners {
+        // Derive Bidder Pot
+        let seeds = &[
+            PREFIX.as_bytes(),
+            &program_id.as_ref(),
+            auction_pubkey.as_ref(),
+            bidder_pubkey.as_ref(),
+        ];
+        let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+        accounts.push(AccountMeta::new_readonly(bidder_pubkey, false));
+        accounts.push(AccountMeta::new(bidder_pot_pubkey, false));
+        accounts.push(AccountMeta::new(bidder_pot_token_pubkey, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: AuctionInstruction::ClaimBids(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an CloseAuction instruction.
+pub fn close_auction_instruction(
+    program_id: Pubkey,
+    payer_pubkey: Pubkey,
+    args: CloseAuctionArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        FEE_SPLITS.as_bytes(),
+    ];
+    let (fee_splits_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new(payer_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new(fee_splits_pubkey, false),
+        ],
+        data: AuctionInstruction::CloseAuction(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an CloseBidderAccounts instruction.
+pub fn close_bidder_accounts_instruction(
+    program_id: Pubkey,
+    signer_pubkey: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    payer_pubkey: Pubkey,
+    close_commitment: bool,
+    args: CloseBidderAccountsArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Metadata key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(signer_pubkey, true),
+        AccountMeta::new_readonly(bidder_pubkey, false),
+        AccountMeta::new(bidder_pot_pubkey, false),
+        AccountMeta::new(bidder_pot_token_pubkey, false),
+        AccountMeta::new(bidder_meta_pubkey, false),
+        AccountMeta::new_readonly(auction_pubkey, false),
+        AccountMeta::new(payer_pubkey, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(auction_extended_pubkey, false),
+    ];
+
+    if close_commitment {
+        // Derive Bidder Commitment
+        let seeds = &[
+            PREFIX.as_bytes(),
+            &program_id.as_ref(),
+            auction_pubkey.as_ref(),
+            bidder_pubkey.as_ref(),
+            "commitment".as_bytes(),
+        ];
+        let (bidder_commitment_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+        accounts.push(AccountMeta::new(bidder_commitment_pubkey, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: AuctionInstruction::CloseBidderAccounts(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an MigrateAccount instruction.
+pub fn migrate_account_instruction(
+    program_id: Pubkey,
+    account_pubkey: Pubkey,
+    payer_pubkey: Pubkey,
+    args: MigrateAccountArgs,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(account_pubkey, false),
+            AccountMeta::new(payer_pubkey, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: AuctionInstruction::MigrateAccount(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Points an instruction built by one of the functions above at another token program, such as
+/// Token-2022, for auctions whose mint belongs to it.
+pub fn with_token_program(mut instruction: Instruction, token_program: Pubkey) -> Instruction {
+    for account in instruction.accounts.iter_mut() {
+        if account.pubkey == spl_token::id() {
+            account.pubkey = token_program;
+        }
+    }
+    instruction
+}
+
+/// Has the wallet that placed a bid sign for it in a CancelBid, RefundExcess or IncreaseBid
+/// instruction built with its bid key as the bidder, for bids a wallet holds beyond its first.
+pub fn with_bid_owner(mut instruction: Instruction, bid_owner: Pubkey) -> Instruction {
+    instruction.accounts[0].is_signer = false;
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(bid_owner, true));
+    instruction
+}
+
+/// Creates an ResizeAuction instruction.
+pub fn resize_auction_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    payer_pubkey: Pubkey,
+    args: ResizeAuctionArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new(payer_pubkey, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::ResizeAuction(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
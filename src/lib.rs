@@ -2,13 +2,25 @@
 
 mod utils;
 
+#[cfg(feature = "client")]
+pub mod client;
 pub mod entrypoint;
 pub mod errors;
 pub mod instruction;
+pub mod merkle;
 pub mod processor;
 
 /// Prefix used in PDA derivations to avoid collisions with other programs.
 pub const PREFIX: &str = "auction";
 
 pub const EXTENDED: &str = "extended";
+
+pub const FEE_SPLITS: &str = "splits";
+
+pub const BID: &str = "bid";
+
+/// Token-2022, bids can be made in its tokens as well as in those of the original token program.
+pub mod token_2022 {
+    solana_program::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBZCXEQ6Nk8d");
+}
 solana_program::declare_id!("auctxRXPeJoc4817jDhf4HbjnhEcr1cCXenosMhK5R8");
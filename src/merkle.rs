@@ -0,0 +1,129 @@
+//! Merkle allowlists restricting who can bid on an auction. Leaves are the keccak hash of a
+//! bidders pubkey, and each node hashes its two children in sorted order so proofs need no
+//! left/right flags. Leaves and nodes hash under different prefixes so one can never pass for the
+//! other.
+//!
+//! The program only verifies proofs, MerkleTree builds the root and proofs off-chain from a list
+//! of allowed pubkeys.
+
+use solana_program::{keccak::hashv, pubkey::Pubkey};
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Hash of an allowed bidder, the bottom layer of the tree.
+pub fn leaf(bidder: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, bidder.as_ref()]).to_bytes()
+}
+
+fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Check the proof leads from the bidder up to the root.
+pub fn verify(root: &[u8; 32], bidder: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf(bidder), |hash, sibling| node(&hash, sibling));
+    computed == *root
+}
+
+/// Tree over a list of allowed bidders, an odd node at the end of a layer is carried up as is.
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(bidders: &[Pubkey]) -> MerkleTree {
+        let mut leaves: Vec<[u8; 32]> = bidders.iter().map(leaf).collect();
+        leaves.sort();
+        leaves.dedup();
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        MerkleTree { layers }
+    }
+
+    /// Root to store on the auction, None for an empty list.
+    pub fn root(&self) -> Option<[u8; 32]> {
+        self.layers.last().unwrap().first().cloned()
+    }
+
+    /// Proof for a bidder to pass in PlaceBidArgsV2, None if they are not on the list.
+    pub fn proof(&self, bidder: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.layers[0].binary_search(&leaf(bidder)).ok()?;
+        let mut proof = vec![];
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bidders(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    fn assert_round_trip(bidders: &[Pubkey]) {
+        let tree = MerkleTree::new(bidders);
+        let root = tree.root().unwrap();
+        for bidder in bidders {
+            let proof = tree.proof(bidder).unwrap();
+            assert!(verify(&root, bidder, &proof));
+        }
+    }
+
+    #[test]
+    fn single_leaf() {
+        let bidders = bidders(1);
+        let tree = MerkleTree::new(&bidders);
+        assert_eq!(tree.root(), Some(leaf(&bidders[0])));
+        assert!(tree.proof(&bidders[0]).unwrap().is_empty());
+        assert_round_trip(&bidders);
+    }
+
+    #[test]
+    fn two_leaves() {
+        assert_round_trip(&bidders(2));
+    }
+
+    #[test]
+    fn odd_number_of_leaves() {
+        assert_round_trip(&bidders(3));
+        assert_round_trip(&bidders(7));
+    }
+
+    #[test]
+    fn non_member() {
+        let bidders = bidders(5);
+        let tree = MerkleTree::new(&bidders);
+        let root = tree.root().unwrap();
+        let outsider = Pubkey::new_unique();
+
+        assert_eq!(tree.proof(&outsider), None);
+        // A member's proof does not carry anyone else up to the root.
+        let proof = tree.proof(&bidders[0]).unwrap();
+        assert!(!verify(&root, &outsider, &proof));
+        assert!(!verify(&root, &bidders[1], &proof));
+    }
+}
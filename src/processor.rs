@@ -1,4 +1,4 @@
-use crate::errors::AuctionError;
+use crate::{errors::AuctionError, BID, PREFIX};
 use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
@@ -8,24 +8,74 @@ use solana_program::{
 use std::{cell::Ref, cmp, mem};
 
 // Declare submodules, each contains a single handler for each instruction variant in the program.
+pub mod accept_authority;
+pub mod cancel_auction;
 pub mod cancel_bid;
 pub mod claim_bid;
+pub mod claim_bids;
+pub mod close_auction;
+pub mod close_bidder_accounts;
+pub mod commit_bid;
 pub mod create_auction;
 pub mod create_auction_v2;
+pub mod create_auction_v3;
+pub mod create_auction_v4;
+pub mod create_auction_v5;
+pub mod create_auction_v6;
 pub mod end_auction;
+pub mod increase_bid;
+pub mod migrate_account;
+pub mod pause_auction;
 pub mod place_bid;
+pub mod place_bid_v2;
+pub mod place_bid_v3;
+pub mod propose_authority;
+pub mod refund_bids;
+pub mod refund_excess;
+pub mod resize_auction;
+pub mod resume_auction;
+pub mod reveal_bid;
 pub mod set_authority;
+pub mod set_authority_roles;
+pub mod set_crank_reward;
 pub mod start_auction;
+pub mod start_scheduled_auction;
+pub mod update_auction;
 
 // Re-export submodules handlers + associated types for other programs to consume.
+pub use accept_authority::*;
+pub use cancel_auction::*;
 pub use cancel_bid::*;
 pub use claim_bid::*;
+pub use claim_bids::*;
+pub use close_auction::*;
+pub use close_bidder_accounts::*;
+pub use commit_bid::*;
 pub use create_auction::*;
 pub use create_auction_v2::*;
+pub use create_auction_v3::*;
+pub use create_auction_v4::*;
+pub use create_auction_v5::*;
+pub use create_auction_v6::*;
 pub use end_auction::*;
+pub use increase_bid::*;
+pub use migrate_account::*;
+pub use pause_auction::*;
 pub use place_bid::*;
+pub use place_bid_v2::*;
+pub use place_bid_v3::*;
+pub use propose_authority::*;
+pub use refund_bids::*;
+pub use refund_excess::*;
+pub use resize_auction::*;
+pub use resume_auction::*;
+pub use reveal_bid::*;
 pub use set_authority::*;
+pub use set_authority_roles::*;
+pub use set_crank_reward::*;
 pub use start_auction::*;
+pub use start_scheduled_auction::*;
+pub use update_auction::*;
 
 pub fn process_instruction(
     program_id: &Pubkey,
@@ -36,17 +86,70 @@ pub fn process_instruction(
     match AuctionInstruction::try_from_slice(input)? {
         AuctionInstruction::CancelBid(args) => cancel_bid(program_id, accounts, args),
         AuctionInstruction::ClaimBid(args) => claim_bid(program_id, accounts, args),
-        AuctionInstruction::CreateAuction(args) => {
-            create_auction(program_id, accounts, args, None, None)
-        }
+        AuctionInstruction::CreateAuction(args) => create_auction(
+            program_id, accounts, args, None, None, None, None, None, None, None, None,
+        ),
         AuctionInstruction::CreateAuctionV2(args) => create_auction_v2(program_id, accounts, args),
         AuctionInstruction::EndAuction(args) => end_auction(program_id, accounts, args),
-        AuctionInstruction::PlaceBid(args) => place_bid(program_id, accounts, args),
+        AuctionInstruction::PlaceBid(args) => place_bid(program_id, accounts, args, None, 0),
         AuctionInstruction::SetAuthority => set_authority(program_id, accounts),
         AuctionInstruction::StartAuction(args) => start_auction(program_id, accounts, args),
+        AuctionInstruction::CommitBid(args) => commit_bid(program_id, accounts, args),
+        AuctionInstruction::RevealBid(args) => reveal_bid(program_id, accounts, args),
+        AuctionInstruction::RefundExcess(args) => refund_excess(program_id, accounts, args),
+        AuctionInstruction::CreateAuctionV3(args) => create_auction_v3(program_id, accounts, args),
+        AuctionInstruction::StartScheduledAuction(args) => {
+            start_scheduled_auction(program_id, accounts, args)
+        }
+        AuctionInstruction::CloseAuction(args) => close_auction(program_id, accounts, args),
+        AuctionInstruction::CloseBidderAccounts(args) => {
+            close_bidder_accounts(program_id, accounts, args)
+        }
+        AuctionInstruction::MigrateAccount(args) => migrate_account(program_id, accounts, args),
+        AuctionInstruction::CreateAuctionV4(args) => create_auction_v4(program_id, accounts, args),
+        AuctionInstruction::CreateAuctionV5(args) => create_auction_v5(program_id, accounts, args),
+        AuctionInstruction::PlaceBidV2(args) => place_bid_v2(program_id, accounts, args),
+        AuctionInstruction::CreateAuctionV6(args) => create_auction_v6(program_id, accounts, args),
+        AuctionInstruction::PlaceBidV3(args) => place_bid_v3(program_id, accounts, args),
+        AuctionInstruction::IncreaseBid(args) => increase_bid(program_id, accounts, args),
+        AuctionInstruction::UpdateAuction(args) => update_auction(program_id, accounts, args),
+        AuctionInstruction::PauseAuction(args) => pause_auction(program_id, accounts, args),
+        AuctionInstruction::ResumeAuction(args) => resume_auction(program_id, accounts, args),
+        AuctionInstruction::ProposeAuthority(args) => propose_authority(program_id, accounts, args),
+        AuctionInstruction::AcceptAuthority(args) => accept_authority(program_id, accounts, args),
+        AuctionInstruction::SetAuthorityRoles(args) => {
+            set_authority_roles(program_id, accounts, args)
+        }
+        AuctionInstruction::CancelAuction(args) => cancel_auction(program_id, accounts, args),
+        AuctionInstruction::SetCrankReward(args) => set_crank_reward(program_id, accounts, args),
+        AuctionInstruction::RefundBids(args) => refund_bids(program_id, accounts, args),
+        AuctionInstruction::ClaimBids(args) => claim_bids(program_id, accounts, args),
+        AuctionInstruction::ResizeAuction(args) => resize_auction(program_id, accounts, args),
     }
 }
 
+/// Leading byte of every account the program owns, naming the type and layout version of the data
+/// that follows it.
+#[repr(C)]
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum Key {
+    Uninitialized,
+    AuctionV1,
+    AuctionExtendedV1,
+    BidderMetadataV1,
+    BidderPotV1,
+    BidderCommitmentV1,
+    AuctionFeeSplitsV1,
+}
+
+/// Check the leading byte of an account against the key of the type being loaded.
+fn assert_key(a: &AccountInfo, key: Key) -> ProgramResult {
+    if a.data_is_empty() || a.data.borrow()[0] != key as u8 {
+        return Err(AuctionError::DataTypeMismatch.into());
+    }
+    Ok(())
+}
+
 /// Structure with pricing floor data.
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
@@ -61,15 +164,78 @@ pub enum PriceFloor {
     BlindedPrice(Hash),
 }
 
+/// Shape of the price curve used by a Dutch auction to move from its start price to its floor
+/// price over the auction window.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum PriceDecay {
+    /// Price falls continuously over the whole window.
+    Linear,
+    /// Price falls in equal steps, one every given number of seconds.
+    Stepped(UnixTimestamp),
+}
+
+/// Price every winner of a uniform price auction pays.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum ClearingRule {
+    /// The lowest of the winning bids.
+    LowestWinningBid,
+    /// The highest bid that did not win, or the price floor if every bid won.
+    HighestLosingBid,
+}
+
+/// Anti-sniping policy, a bid placed close to the end time pushes the end time back so that other
+/// bidders get a chance to answer it.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct AntiSnipingPolicy {
+    /// Bids placed within this many seconds of the end time extend the auction.
+    pub window: UnixTimestamp,
+    /// Seconds each such bid adds to the end time.
+    pub extension: UnixTimestamp,
+    /// Most seconds the end time can be pushed back by, over all extensions.
+    pub max_total_extension: UnixTimestamp,
+    /// Most times the end time can be pushed back.
+    pub max_extensions: u8,
+}
+
+/// Bidding mechanism an auction may be created with. When no type is given the mechanism is
+/// derived from the WinnerLimit, capped auctions being English auctions and unlimited auctions
+/// being open editions.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum AuctionType {
+    /// Descending price auction, the first bid at or above the current price wins.
+    Dutch {
+        start_price: u64,
+        floor_price: u64,
+        decay: PriceDecay,
+    },
+    /// Sealed-bid auction, bidders commit to a hashed bid and reveal it once bidding closes. The
+    /// winners pay the highest losing revealed bid.
+    SealedBid {
+        /// Seconds after the end of the commit phase during which bids can be revealed.
+        reveal_window: UnixTimestamp,
+        /// Part of each pot forfeited to the auction authority if the bid is never revealed.
+        deposit: u64,
+    },
+    /// English auction where every winner pays the same clearing price and may withdraw the rest
+    /// of their bid.
+    UniformPrice(ClearingRule),
+}
+
 // The two extra 8's are present, one 8 is for the Vec's amount of elements and one is for the max
 // usize in bid state.
 // NOTE: New research suggests u32s are used for vecs in borsh, not u64s, so the first extra 8 should be a 4
 // but for legacy reasons we leave it behind.
-pub const BASE_AUCTION_DATA_SIZE: usize = 32 + 32 + 9 + 9 + 9 + 9 + 1 + 32 + 1 + 8 + 8 + 8;
+pub const BASE_AUCTION_DATA_SIZE: usize = 1 + 32 + 32 + 9 + 9 + 9 + 9 + 1 + 32 + 1 + 8 + 8 + 8;
 pub const BID_LENGTH: usize = 32 + 8;
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct AuctionData {
+    /// Account type and layout version, always Key::AuctionV1.
+    pub key: Key,
     /// Pubkey of the authority with permission to modify this auction.
     pub authority: Pubkey,
     /// Pubkey of the resource being bid on.
@@ -95,16 +261,97 @@ pub struct AuctionData {
     pub bid_state: BidState,
 }
 
+/// The fields at the front of AuctionData, up to the kind of auction but none of its bids. Reading
+/// only these keeps instructions that deal with many bids at once within the compute budget.
+#[repr(C)]
+#[derive(Clone, BorshDeserialize, PartialEq, Debug)]
+pub struct AuctionDataHeader {
+    pub key: Key,
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub last_bid: Option<UnixTimestamp>,
+    pub ended_at: Option<UnixTimestamp>,
+    pub end_auction_at: Option<UnixTimestamp>,
+    pub end_auction_gap: Option<UnixTimestamp>,
+    pub price_floor: PriceFloor,
+    pub state: AuctionState,
+    /// Variant of the BidState that follows, 0 being an English auction.
+    pub bid_state_kind: u8,
+}
+
+impl AuctionDataHeader {
+    /// Whether an English auction is over at the given time, see AuctionData::ended.
+    pub fn ended(&self, now: UnixTimestamp) -> Result<bool, ProgramError> {
+        match ended_by_state(&self.state) {
+            Some(ended) => Ok(ended),
+            None => ended_by_time(self.ended_at, self.end_auction_gap, self.last_bid, now),
+        }
+    }
+
+    /// Lowest bid that can win.
+    pub fn minimum_price(&self) -> u64 {
+        match self.price_floor {
+            PriceFloor::MinimumPrice(min) => min[0],
+            _ => 0,
+        }
+    }
+}
+
+/// Whether an auction in the given state is over regardless of its timing. The clock stops for a
+/// paused auction, and a cancelled one is over for good.
+fn ended_by_state(state: &AuctionState) -> Option<bool> {
+    match state {
+        AuctionState::Paused => Some(false),
+        AuctionState::Cancelled => Some(true),
+        _ => None,
+    }
+}
+
+/// Whether an auction with the given timing is over at the given time.
+fn ended_by_time(
+    ended_at: Option<UnixTimestamp>,
+    end_auction_gap: Option<UnixTimestamp>,
+    last_bid: Option<UnixTimestamp>,
+    now: UnixTimestamp,
+) -> Result<bool, ProgramError> {
+    match (ended_at, end_auction_gap) {
+        // NOTE if changing this, change in auction.ts on front end as well where logic duplicates.
+        // Both end and gap present, means a bid can still be placed post-auction if it is
+        // within the gap time.
+        (Some(end), Some(gap)) => {
+            // Check if the bid is within the gap between the last bidder.
+            if let Some(last) = last_bid {
+                let next_bid_time = last
+                    .checked_add(gap)
+                    .ok_or(AuctionError::NumericalOverflowError)?;
+
+                Ok(now > end && now > next_bid_time)
+            } else {
+                Ok(now > end)
+            }
+        }
+
+        // Simply whether now has passed the end.
+        (Some(end), None) => Ok(now > end),
+
+        // No other end conditions.
+        _ => Ok(false),
+    }
+}
+
 // Alias for auction name.
 pub type AuctionName = [u8; 32];
 
-pub const MAX_AUCTION_DATA_EXTENDED_SIZE: usize = 8 + 9 + 2 + 9 + 33 + 158;
+pub const MAX_AUCTION_DATA_EXTENDED_SIZE: usize =
+    1 + 8 + 9 + 2 + 9 + 33 + 9 + 26 + 1 + 8 + 33 + 8 + 1 + 33 + 2 + 9 + 33 + 33 + 33 + 33 + 2 + 8 + 18;
 // Further storage for more fields. Would like to store more on the main data but due
 // to a borsh issue that causes more added fields to inflict "Access violation" errors
 // during redemption in main Metaplex app for no reason, we had to add this nasty PDA.
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct AuctionDataExtended {
+    /// Account type and layout version, always Key::AuctionExtendedV1.
+    pub key: Key,
     /// Total uncancelled bids
     pub total_uncancelled_bids: u64,
     // Unimplemented fields
@@ -116,6 +363,39 @@ pub struct AuctionDataExtended {
     pub instant_sale_price: Option<u64>,
     /// Auction name
     pub name: Option<AuctionName>,
+    /// Time the auction opens by itself, without waiting for StartAuction.
+    pub start_auction_at: Option<UnixTimestamp>,
+    /// Anti-sniping policy
+    pub anti_sniping: Option<AntiSnipingPolicy>,
+    /// Number of times a late bid has extended the auction
+    pub extensions: u8,
+    /// Seconds the end time has been pushed back by late bids
+    pub total_extension: UnixTimestamp,
+    /// Account that paid for the auction accounts and gets the rent back when they are closed
+    pub payer: Option<Pubkey>,
+    /// Bidder pots still holding funds owed to the bidder or the authority
+    pub funded_pots: u64,
+    /// Whether ClaimBid pays out through the fee splits stored next to the auction
+    pub has_fee_splits: bool,
+    /// Merkle root of the bidders allowed to place bids, anyone can bid when unset
+    pub allowlist_root: Option<[u8; 32]>,
+    /// How many bids one wallet may hold at once, and so how many slots it can win, one when unset
+    pub max_bids_per_bidder: Option<u8>,
+    /// When a paused auction was paused, the time since is added to its end when it resumes
+    pub paused_at: Option<UnixTimestamp>,
+    /// Authority proposed by the current one, takes over once it accepts
+    pub pending_authority: Option<Pubkey>,
+    /// Key allowed to claim bids besides the authority
+    pub claimer: Option<Pubkey>,
+    /// Key allowed to start and end the auction besides the authority
+    pub starter: Option<Pubkey>,
+    /// Authority that proposed the pending authority and delegated the roles, none of them count
+    /// once the authority has changed
+    pub delegated_by: Option<Pubkey>,
+    /// Share of each losing bid, in basis points, paid to whoever refunds it with RefundBids
+    pub crank_reward_basis_points: u16,
+    /// Bidder pots opened and not yet closed with CloseBidderAccounts
+    pub open_bidder_pots: u64,
 }
 
 impl AuctionDataExtended {
@@ -123,12 +403,62 @@ impl AuctionDataExtended {
         if a.data_len() != MAX_AUCTION_DATA_EXTENDED_SIZE {
             return Err(AuctionError::DataTypeMismatch.into());
         }
+        assert_key(a, Key::AuctionExtendedV1)?;
 
         let auction_extended: AuctionDataExtended = try_from_slice_unchecked(&a.data.borrow_mut())?;
 
         Ok(auction_extended)
     }
 
+    /// Key a role is delegated to, if the current authority delegated it.
+    pub fn delegate(&self, authority: &Pubkey, role: Option<Pubkey>) -> Option<Pubkey> {
+        if self.delegated_by.as_ref() == Some(authority) {
+            role
+        } else {
+            None
+        }
+    }
+
+    /// Prepare to delegate on behalf of the authority, dropping whatever an earlier authority
+    /// delegated.
+    pub fn delegate_for(&mut self, authority: &Pubkey) {
+        if self.delegated_by.as_ref() != Some(authority) {
+            self.pending_authority = None;
+            self.claimer = None;
+            self.starter = None;
+            self.delegated_by = Some(*authority);
+        }
+    }
+
+    /// Record a bid moving funds into a bidder pot.
+    pub fn pot_funded(&mut self) -> ProgramResult {
+        self.funded_pots = self
+            .funded_pots
+            .checked_add(1)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        Ok(())
+    }
+
+    /// Record a bidder pot being settled with both the bidder and the authority. Auctions created
+    /// before the count was kept start from zero, so this never goes below it.
+    pub fn pot_drained(&mut self) {
+        self.funded_pots = self.funded_pots.saturating_sub(1);
+    }
+
+    /// Record a bidder pot account being created.
+    pub fn pot_opened(&mut self) -> ProgramResult {
+        self.open_bidder_pots = self
+            .open_bidder_pots
+            .checked_add(1)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        Ok(())
+    }
+
+    /// Record a bidder pot account being closed along with the other accounts of its bid.
+    pub fn pot_closed(&mut self) {
+        self.open_bidder_pots = self.open_bidder_pots.saturating_sub(1);
+    }
+
     pub fn get_instant_sale_price<'a>(data: &'a Ref<'a, &'a mut [u8]>) -> Option<u64> {
         if let Some(idx) = Self::find_instant_sale_beginning(data) {
             Some(u64::from_le_bytes(*array_ref![data, idx, 8]))
@@ -138,8 +468,8 @@ impl AuctionDataExtended {
     }
 
     fn find_instant_sale_beginning<'a>(data: &'a Ref<'a, &'a mut [u8]>) -> Option<usize> {
-        // total_uncancelled_bids + tick_size Option
-        let mut instant_sale_beginning = 8;
+        // key + total_uncancelled_bids + tick_size Option
+        let mut instant_sale_beginning = 1 + 8;
 
         // gaps for tick_size and gap_tick_size_percentage
         let gaps = [9, 2];
@@ -164,9 +494,15 @@ impl AuctionDataExtended {
 impl AuctionData {
     // Cheap methods to get at AuctionData without supremely expensive borsh deserialization calls.
 
+    pub fn get_header(a: &AccountInfo) -> Result<AuctionDataHeader, ProgramError> {
+        assert_key(a, Key::AuctionV1)?;
+        let header = AuctionDataHeader::deserialize(&mut &a.data.borrow()[..])?;
+        Ok(header)
+    }
+
     pub fn get_token_mint(a: &AccountInfo) -> Pubkey {
         let data = a.data.borrow();
-        let token_mint_data = array_ref![data, 32, 32];
+        let token_mint_data = array_ref![data, 1 + 32, 32];
         Pubkey::new_from_array(*token_mint_data)
     }
 
@@ -178,6 +514,8 @@ impl AuctionData {
             0 => Ok(AuctionState::Created),
             1 => Ok(AuctionState::Started),
             2 => Ok(AuctionState::Ended),
+            3 => Ok(AuctionState::Paused),
+            4 => Ok(AuctionState::Cancelled),
             _ => Err(ProgramError::InvalidAccountData),
         }
     }
@@ -189,7 +527,7 @@ impl AuctionData {
 
     fn find_bid_state_beginning(a: &AccountInfo) -> usize {
         let data = a.data.borrow();
-        let mut bid_state_beginning = 32 + 32;
+        let mut bid_state_beginning = 1 + 32 + 32;
 
         for i in 0..4 {
             // One for each unix timestamp
@@ -219,6 +557,10 @@ impl AuctionData {
     }
 
     pub fn get_is_winner(a: &AccountInfo, key: &Pubkey) -> Option<usize> {
+        // Nobody wins a cancelled auction.
+        if let Ok(AuctionState::Cancelled) = AuctionData::get_state(a) {
+            return None;
+        }
         let bid_state_beginning = AuctionData::find_bid_state_beginning(a);
         let data = a.data.borrow();
         let as_bytes = key.to_bytes();
@@ -252,6 +594,9 @@ impl AuctionData {
     }
 
     pub fn get_winner_at(a: &AccountInfo, idx: usize) -> Option<Pubkey> {
+        if let Ok(AuctionState::Cancelled) = AuctionData::get_state(a) {
+            return None;
+        }
         let (bid_state_beginning, num_elements, max) = AuctionData::get_vec_info(a);
         match AuctionData::get_winner_at_inner(
             &a.data.borrow(),
@@ -314,43 +659,59 @@ impl AuctionData {
     }
 
     pub fn from_account_info(a: &AccountInfo) -> Result<AuctionData, ProgramError> {
-        if (a.data_len() - BASE_AUCTION_DATA_SIZE) % mem::size_of::<Bid>() != 0 {
+        if a.data_len() < BASE_AUCTION_DATA_SIZE
+            || (a.data_len() - BASE_AUCTION_DATA_SIZE) % mem::size_of::<Bid>() != 0
+        {
             return Err(AuctionError::DataTypeMismatch.into());
         }
+        assert_key(a, Key::AuctionV1)?;
 
         let auction: AuctionData = try_from_slice_unchecked(&a.data.borrow_mut())?;
 
         Ok(auction)
     }
 
+    /// Whether bids are paid in lamports held by the bidder pots themselves, selected by creating
+    /// the auction with the native mint.
+    pub fn is_native(&self) -> bool {
+        self.token_mint == spl_token::native_mint::id()
+    }
+
+    /// Whether the auction is over at the given time. Auctions created with a start time have
+    /// `ended_at` set from that start time onwards, so they end on schedule even if nobody ever
+    /// sent the transition to Started.
     pub fn ended(&self, now: UnixTimestamp) -> Result<bool, ProgramError> {
-        // If there is an end time specified, handle conditions.
-        return match (self.ended_at, self.end_auction_gap) {
-            // NOTE if changing this, change in auction.ts on front end as well where logic duplicates.
-            // Both end and gap present, means a bid can still be placed post-auction if it is
-            // within the gap time.
-            (Some(end), Some(gap)) => {
-                // Check if the bid is within the gap between the last bidder.
-                if let Some(last) = self.last_bid {
-                    let next_bid_time = last
-                        .checked_add(gap)
-                        .ok_or(AuctionError::NumericalOverflowError)?;
-
-                    Ok(now > end && now > next_bid_time)
-                } else {
-                    Ok(now > end)
-                }
+        if let Some(ended) = ended_by_state(&self.state) {
+            return Ok(ended);
+        }
+
+        // A Dutch auction is over as soon as somebody accepts the price.
+        if let BidState::DutchAuction { bids, .. } = &self.bid_state {
+            if !bids.is_empty() {
+                return Ok(true);
             }
+        }
 
-            // Simply whether now has passed the end.
-            (Some(end), None) => Ok(now > end),
+        // A sealed-bid auction stays open past its end time while bids are revealed.
+        if let BidState::SealedBid { reveal_window, .. } = &self.bid_state {
+            return match self.ended_at {
+                Some(end) => Ok(now
+                    > end
+                        .checked_add(*reveal_window)
+                        .ok_or(AuctionError::NumericalOverflowError)?),
+                None => Ok(false),
+            };
+        }
 
-            // No other end conditions.
-            _ => Ok(false),
-        };
+        // If there is an end time specified, handle conditions.
+        ended_by_time(self.ended_at, self.end_auction_gap, self.last_bid, now)
     }
 
     pub fn is_winner(&self, key: &Pubkey) -> Option<usize> {
+        // Nobody wins a cancelled auction.
+        if self.state == AuctionState::Cancelled {
+            return None;
+        }
         let minimum = match self.price_floor {
             PriceFloor::MinimumPrice(min) => min[0],
             _ => 0,
@@ -358,6 +719,18 @@ impl AuctionData {
         self.bid_state.is_winner(key, minimum)
     }
 
+    /// Same as is_winner, for a bid whose amount is known from its bidder metadata.
+    pub fn is_winning_bid(&self, bid: &Bid) -> Option<usize> {
+        if self.state == AuctionState::Cancelled {
+            return None;
+        }
+        let minimum = match self.price_floor {
+            PriceFloor::MinimumPrice(min) => min[0],
+            _ => 0,
+        };
+        self.bid_state.is_winning_bid(bid, minimum)
+    }
+
     pub fn num_winners(&self) -> u64 {
         self.bid_state.num_winners()
     }
@@ -367,6 +740,9 @@ impl AuctionData {
     }
 
     pub fn winner_at(&self, idx: usize) -> Option<Pubkey> {
+        if self.state == AuctionState::Cancelled {
+            return None;
+        }
         self.bid_state.winner_at(idx)
     }
 
@@ -383,6 +759,250 @@ impl AuctionData {
         }
     }
 
+    /// End time of an auction that starts at the given time, None if it has no end time.
+    pub fn end_time_from(
+        &self,
+        start: UnixTimestamp,
+    ) -> Result<Option<UnixTimestamp>, ProgramError> {
+        match self.end_auction_at {
+            Some(end_auction_at) => match start.checked_add(end_auction_at) {
+                Some(val) => Ok(Some(val)),
+                None => Err(AuctionError::NumericalOverflowError.into()),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Move the auction to Started. The end time runs from the scheduled start when there is one,
+    /// so it does not matter how late the transition is sent, and from now otherwise.
+    pub fn start(
+        &mut self,
+        now: UnixTimestamp,
+        start_auction_at: Option<UnixTimestamp>,
+    ) -> ProgramResult {
+        self.state = self.state.clone().start(now, start_auction_at)?;
+        self.ended_at = self.end_time_from(start_auction_at.unwrap_or(now))?;
+        Ok(())
+    }
+
+    /// Start a scheduled auction whose start time has passed, returning whether it was started.
+    pub fn start_if_scheduled(
+        &mut self,
+        now: UnixTimestamp,
+        start_auction_at: Option<UnixTimestamp>,
+    ) -> Result<bool, ProgramError> {
+        match start_auction_at {
+            Some(start) if self.state == AuctionState::Created && now >= start => {
+                self.start(now, start_auction_at)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Push the end time back if a bid placed at the given time falls within the final window of the
+    /// anti-sniping policy, returning whether it did so the extended data can be saved.
+    pub fn extend_for_late_bid(
+        &mut self,
+        now: UnixTimestamp,
+        auction_extended: &mut AuctionDataExtended,
+    ) -> Result<bool, ProgramError> {
+        let (policy, end) = match (&auction_extended.anti_sniping, self.ended_at) {
+            (Some(policy), Some(end)) => (policy, end),
+            _ => return Ok(false),
+        };
+
+        // Bids let in by the end gap are already past the end time and are not extended, nor is an
+        // auction that an instant sale has just closed.
+        if self.state == AuctionState::Ended
+            || now > end
+            || end.saturating_sub(now) >= policy.window
+        {
+            return Ok(false);
+        }
+
+        if auction_extended.extensions >= policy.max_extensions {
+            return Ok(false);
+        }
+
+        let extension = cmp::min(
+            policy.extension,
+            policy
+                .max_total_extension
+                .saturating_sub(auction_extended.total_extension),
+        );
+        if extension <= 0 {
+            return Ok(false);
+        }
+
+        self.ended_at = Some(
+            end.checked_add(extension)
+                .ok_or(AuctionError::NumericalOverflowError)?,
+        );
+        auction_extended.extensions += 1;
+        auction_extended.total_extension = auction_extended
+            .total_extension
+            .checked_add(extension)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        msg!("Late bid extended the auction by {:?}", extension);
+
+        Ok(true)
+    }
+
+    /// Abort an auction that is not over so nobody wins it. Bids at the instant sale price are
+    /// final and may already have been claimed, so an auction holding one cannot be cancelled.
+    pub fn cancel(&mut self, now: UnixTimestamp, instant_sale_price: Option<u64>) -> ProgramResult {
+        if self.ended(now)? {
+            return Err(AuctionError::InvalidState.into());
+        }
+        if let Some(price) = instant_sale_price {
+            for idx in 0..self.num_winners() as usize {
+                if self.bid_state.amount(idx) >= price {
+                    msg!("Winner {:?} bid the instant sale price", idx);
+                    return Err(AuctionError::InvalidState.into());
+                }
+            }
+        }
+        self.state = self.state.clone().cancel()?;
+        Ok(())
+    }
+
+    /// Freeze a running auction, no bids are taken until it resumes.
+    pub fn pause(
+        &mut self,
+        now: UnixTimestamp,
+        auction_extended: &mut AuctionDataExtended,
+    ) -> ProgramResult {
+        if self.ended(now)? {
+            return Err(AuctionError::InvalidState.into());
+        }
+        self.state = self.state.clone().pause()?;
+        auction_extended.paused_at = Some(now);
+        Ok(())
+    }
+
+    /// Resume a paused auction, pushing the end time and the last bid back by as long as it was
+    /// paused so neither the end nor the gap after the last bid cost bidders any time.
+    pub fn resume(
+        &mut self,
+        now: UnixTimestamp,
+        auction_extended: &mut AuctionDataExtended,
+    ) -> ProgramResult {
+        self.state = self.state.clone().resume()?;
+        let paused_at = auction_extended
+            .paused_at
+            .take()
+            .ok_or(AuctionError::InvalidState)?;
+        let paused_for = now.saturating_sub(paused_at);
+
+        if let Some(end) = self.ended_at {
+            self.ended_at = Some(
+                end.checked_add(paused_for)
+                    .ok_or(AuctionError::NumericalOverflowError)?,
+            );
+        }
+        if let Some(last) = self.last_bid {
+            self.last_bid = Some(
+                last.checked_add(paused_for)
+                    .ok_or(AuctionError::NumericalOverflowError)?,
+            );
+        }
+        msg!("Auction resumed after {:?} seconds", paused_for);
+        Ok(())
+    }
+
+    /// Whether a sealed-bid auction is accepting commitments at the given time.
+    pub fn in_commit_phase(&self, now: UnixTimestamp) -> bool {
+        self.state == AuctionState::Started && self.ended_at.map_or(true, |end| now <= end)
+    }
+
+    /// Whether a sealed-bid auction is accepting reveals at the given time.
+    pub fn in_reveal_phase(&self, now: UnixTimestamp) -> Result<bool, ProgramError> {
+        match self.ended_at {
+            Some(end) if self.state != AuctionState::Created => Ok(now > end && !self.ended(now)?),
+            _ => Ok(false),
+        }
+    }
+
+    /// Price paid by every winner of a sealed-bid or uniform price auction, None for any other kind
+    /// of auction where winners pay their own bid.
+    pub fn clearing_price(&self) -> Option<u64> {
+        let minimum = match self.price_floor {
+            PriceFloor::MinimumPrice(min) => min[0],
+            _ => 0,
+        };
+        self.bid_state.clearing_price(minimum)
+    }
+
+    /// Number of bids the auction account has room for, beyond which the lowest bid drops out.
+    /// Fixed at creation from the winner limit, and raised by ResizeAuction.
+    pub fn bid_capacity(&self, a: &AccountInfo) -> usize {
+        (a.data_len().saturating_sub(BASE_AUCTION_DATA_SIZE) / mem::size_of::<Bid>())
+            .saturating_sub(self.bid_state.trailing_slots())
+    }
+
+    /// Record a revealed sealed bid, ordering it amongst the other revealed bids.
+    pub fn reveal_bid(
+        &mut self,
+        bid: Bid,
+        tick_size: Option<u64>,
+        bid_capacity: usize,
+    ) -> Result<(), ProgramError> {
+        let minimum = match self.price_floor {
+            PriceFloor::MinimumPrice(min) => min[0],
+            _ => 0,
+        };
+        self.bid_state.place_bid(
+            bid,
+            tick_size,
+            None,
+            minimum,
+            None,
+            bid_capacity,
+            &mut self.state,
+        )
+    }
+
+    /// Asking price of a Dutch auction at the given time, None for every other kind of auction.
+    /// The decay window runs from the auction start to `ended_at`, which StartAuction computed
+    /// from `end_auction_at`.
+    pub fn dutch_price(&self, now: UnixTimestamp) -> Result<Option<u64>, ProgramError> {
+        let (start_price, floor_price, decay) = match &self.bid_state {
+            BidState::DutchAuction {
+                start_price,
+                floor_price,
+                decay,
+                ..
+            } => (*start_price, *floor_price, decay),
+            _ => return Ok(None),
+        };
+
+        let (end, window) = match (self.ended_at, self.end_auction_at) {
+            (Some(end), Some(window)) if window > 0 => (end, window),
+            // Not started yet, so the price has not begun to fall.
+            _ => return Ok(Some(start_price)),
+        };
+        let start = end
+            .checked_sub(window)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+
+        let mut elapsed = cmp::min(cmp::max(now.saturating_sub(start), 0), window);
+        if let PriceDecay::Stepped(interval) = decay {
+            if *interval > 0 && elapsed < window {
+                elapsed -= elapsed % interval;
+            }
+        }
+
+        // Use u128 so the multiplication cannot overflow before we divide.
+        let drop = ((start_price - floor_price) as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(AuctionError::NumericalOverflowError)?
+            .checked_div(window as u128)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+
+        Ok(Some(start_price - drop as u64))
+    }
+
     pub fn place_bid(
         &mut self,
         bid: Bid,
@@ -390,7 +1010,15 @@ impl AuctionData {
         gap_tick_size_percentage: Option<u8>,
         now: UnixTimestamp,
         instant_sale_price: Option<u64>,
+        bid_capacity: usize,
     ) -> Result<(), ProgramError> {
+        if let Some(price) = self.dutch_price(now)? {
+            if bid.1 < price {
+                msg!("Bid {:?} is below the current price of {:?}", bid.1, price);
+                return Err(AuctionError::BidTooSmall.into());
+            }
+        }
+
         let gap_val = match self.ended_at {
             Some(end) => {
                 // We use the actual gap tick size perc if we're in gap window,
@@ -414,13 +1042,48 @@ impl AuctionData {
             gap_val,
             minimum,
             instant_sale_price,
+            bid_capacity,
             &mut self.state,
         )?;
 
+        // The winning Dutch bid ends the auction there and then.
+        if let BidState::DutchAuction { .. } = self.bid_state {
+            self.ended_at = Some(now);
+        }
+
         self.consider_instant_bid(instant_sale_price);
 
         Ok(())
     }
+
+    /// Raise a bid already in the auction from its current amount to a new total, moving it to its
+    /// new place among the bids. The new total is held to the same tick size and gap rules as a
+    /// fresh bid.
+    pub fn increase_bid(
+        &mut self,
+        current_amount: u64,
+        bid: Bid,
+        tick_size: Option<u64>,
+        gap_tick_size_percentage: Option<u8>,
+        now: UnixTimestamp,
+        instant_sale_price: Option<u64>,
+        bid_capacity: usize,
+    ) -> Result<(), ProgramError> {
+        match self.bid_state {
+            BidState::EnglishAuction { .. } | BidState::UniformPriceAuction { .. } => (),
+            _ => return Err(AuctionError::BidCannotBeIncreased.into()),
+        }
+
+        self.bid_state.cancel_bid(&Bid(bid.0, current_amount))?;
+        self.place_bid(
+            bid,
+            tick_size,
+            gap_tick_size_percentage,
+            now,
+            instant_sale_price,
+            bid_capacity,
+        )
+    }
 }
 
 /// Define valid auction state transitions.
@@ -430,6 +1093,8 @@ pub enum AuctionState {
     Created,
     Started,
     Ended,
+    Paused,
+    Cancelled,
 }
 
 impl AuctionState {
@@ -438,9 +1103,16 @@ impl AuctionState {
     }
 
     #[inline(always)]
-    pub fn start(self) -> Result<Self, ProgramError> {
-        match self {
-            AuctionState::Created => Ok(AuctionState::Started),
+    pub fn start(
+        self,
+        now: UnixTimestamp,
+        start_auction_at: Option<UnixTimestamp>,
+    ) -> Result<Self, ProgramError> {
+        match (self, start_auction_at) {
+            (AuctionState::Created, Some(start)) if now < start => {
+                Err(AuctionError::AuctionNotYetStarted.into())
+            }
+            (AuctionState::Created, _) => Ok(AuctionState::Started),
             _ => Err(AuctionError::AuctionTransitionInvalid.into()),
         }
     }
@@ -450,6 +1122,33 @@ impl AuctionState {
         match self {
             AuctionState::Started => Ok(AuctionState::Ended),
             AuctionState::Created => Ok(AuctionState::Ended),
+            AuctionState::Paused => Ok(AuctionState::Ended),
+            _ => Err(AuctionError::AuctionTransitionInvalid.into()),
+        }
+    }
+
+    #[inline(always)]
+    pub fn cancel(self) -> Result<Self, ProgramError> {
+        match self {
+            AuctionState::Created => Ok(AuctionState::Cancelled),
+            AuctionState::Started => Ok(AuctionState::Cancelled),
+            AuctionState::Paused => Ok(AuctionState::Cancelled),
+            _ => Err(AuctionError::AuctionTransitionInvalid.into()),
+        }
+    }
+
+    #[inline(always)]
+    pub fn pause(self) -> Result<Self, ProgramError> {
+        match self {
+            AuctionState::Started => Ok(AuctionState::Paused),
+            _ => Err(AuctionError::AuctionTransitionInvalid.into()),
+        }
+    }
+
+    #[inline(always)]
+    pub fn resume(self) -> Result<Self, ProgramError> {
+        match self {
+            AuctionState::Paused => Ok(AuctionState::Started),
             _ => Err(AuctionError::AuctionTransitionInvalid.into()),
         }
     }
@@ -465,8 +1164,32 @@ pub struct Bid(pub Pubkey, pub u64);
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub enum BidState {
-    EnglishAuction { bids: Vec<Bid>, max: usize },
-    OpenEdition { bids: Vec<Bid>, max: usize },
+    EnglishAuction {
+        bids: Vec<Bid>,
+        max: usize,
+    },
+    OpenEdition {
+        bids: Vec<Bid>,
+        max: usize,
+    },
+    DutchAuction {
+        bids: Vec<Bid>,
+        max: usize,
+        start_price: u64,
+        floor_price: u64,
+        decay: PriceDecay,
+    },
+    SealedBid {
+        bids: Vec<Bid>,
+        max: usize,
+        reveal_window: UnixTimestamp,
+        deposit: u64,
+    },
+    UniformPriceAuction {
+        bids: Vec<Bid>,
+        max: usize,
+        rule: ClearingRule,
+    },
 }
 
 /// Bidding Implementations.
@@ -476,6 +1199,15 @@ pub enum BidState {
 ///
 /// Open Edition: All bids are accepted, cancellations return money to the bidder and always
 /// succeed.
+///
+/// Dutch Auction: the price falls from a start price to a floor price over the auction window,
+/// the first bid meeting the current price is the single winner and ends the auction.
+///
+/// Sealed Bid: only revealed bids are stored, ordered the same way as an English auction. The
+/// winners each pay the highest losing revealed bid.
+///
+/// Uniform Price Auction: bids exactly as an English auction, but every winner pays the same
+/// clearing price once it has ended.
 impl BidState {
     pub fn new_english(n: usize) -> Self {
         BidState::EnglishAuction {
@@ -491,6 +1223,44 @@ impl BidState {
         }
     }
 
+    pub fn new_dutch(start_price: u64, floor_price: u64, decay: PriceDecay) -> Self {
+        BidState::DutchAuction {
+            bids: vec![],
+            max: 1,
+            start_price,
+            floor_price,
+            decay,
+        }
+    }
+
+    pub fn new_sealed_bid(n: usize, reveal_window: UnixTimestamp, deposit: u64) -> Self {
+        BidState::SealedBid {
+            bids: vec![],
+            max: n,
+            reveal_window,
+            deposit,
+        }
+    }
+
+    pub fn new_uniform_price(n: usize, rule: ClearingRule) -> Self {
+        BidState::UniformPriceAuction {
+            bids: vec![],
+            max: n,
+            rule,
+        }
+    }
+
+    /// Slots at the end of the auction account taken by the fields stored after the bids, sized
+    /// in whole bids as CreateAuction allocates them.
+    pub fn trailing_slots(&self) -> usize {
+        match self {
+            BidState::DutchAuction { .. }
+            | BidState::SealedBid { .. }
+            | BidState::UniformPriceAuction { .. } => 1,
+            _ => 0,
+        }
+    }
+
     pub fn max_array_size_for(n: usize) -> usize {
         let mut real_max = n;
         if real_max < 8 {
@@ -501,6 +1271,69 @@ impl BidState {
         real_max
     }
 
+    /// Check the settings that can still change once an auction is created suit its bidding
+    /// mechanism, on creation and on every update.
+    pub fn assert_valid_settings(
+        &self,
+        end_auction_at: Option<UnixTimestamp>,
+        tick_size: Option<u64>,
+        gap_tick_size_percentage: Option<u8>,
+        instant_sale_price: Option<u64>,
+        anti_sniping: &Option<AntiSnipingPolicy>,
+    ) -> ProgramResult {
+        match self {
+            BidState::DutchAuction { .. } => {
+                if end_auction_at.is_none()
+                    || tick_size.is_some()
+                    || gap_tick_size_percentage.is_some()
+                {
+                    return Err(AuctionError::InvalidDutchAuctionConfig.into());
+                }
+            }
+            BidState::SealedBid { .. } => {
+                if end_auction_at.is_none()
+                    || gap_tick_size_percentage.is_some()
+                    || instant_sale_price.is_some()
+                {
+                    return Err(AuctionError::InvalidSealedBidConfig.into());
+                }
+            }
+            // The clearing price is only known once the auction ends, so nothing can be settled
+            // early through an instant sale.
+            BidState::UniformPriceAuction { .. } => {
+                if instant_sale_price.is_some() {
+                    return Err(AuctionError::InvalidUniformPriceConfig.into());
+                }
+            }
+            _ => (),
+        }
+
+        // Late bids can only push back a fixed end time, and Dutch and sealed-bid auctions do not
+        // take competing bids in the open.
+        if let Some(policy) = anti_sniping {
+            let open_bidding = match self {
+                BidState::DutchAuction { .. } | BidState::SealedBid { .. } => false,
+                _ => true,
+            };
+            if end_auction_at.is_none()
+                || policy.window <= 0
+                || policy.extension <= 0
+                || policy.max_total_extension < 0
+                || !open_bidding
+            {
+                return Err(AuctionError::InvalidAntiSnipingPolicy.into());
+            }
+        }
+
+        if let Some(gap_tick) = gap_tick_size_percentage {
+            if gap_tick > 100 {
+                return Err(AuctionError::InvalidGapTickSizePercentage.into());
+            }
+        }
+
+        Ok(())
+    }
+
     fn assert_valid_tick_size_bid(bid: &Bid, tick_size: Option<u64>) -> ProgramResult {
         if let Some(tick) = tick_size {
             if bid.1.checked_rem(tick) != Some(0) {
@@ -549,6 +1382,7 @@ impl BidState {
         gap_tick_size_percentage: Option<u8>,
         minimum: u64,
         instant_sale_price: Option<u64>,
+        bid_capacity: usize,
         auction_state: &mut AuctionState,
     ) -> Result<(), ProgramError> {
         msg!("Placing bid {:?}", &bid.1.to_string());
@@ -558,85 +1392,88 @@ impl BidState {
         }
 
         match self {
-            // In a capped auction, track the limited number of winners.
-            BidState::EnglishAuction { ref mut bids, max } => {
-                match bids.last() {
-                    Some(top) => {
-                        msg!("Looking to go over the loop, but check tick size first");
-
-                        for i in (0..bids.len()).rev() {
-                            msg!("Comparison of {:?} and {:?} for {:?}", bids[i].1, bid.1, i);
-                            if bids[i].1 < bid.1 {
-                                if let Some(gap_tick) = gap_tick_size_percentage {
-                                    BidState::assert_valid_gap_insertion(gap_tick, &bids[i], &bid)?
-                                }
-
-                                msg!("Ok we can do an insert");
-                                if i + 1 < bids.len() {
-                                    msg!("Doing a normal insert");
-                                    bids.insert(i + 1, bid);
-                                } else {
-                                    msg!("Doing an on the end insert");
-                                    bids.push(bid)
-                                }
-                                break;
-                            } else if bids[i].1 == bid.1 {
-                                if let Some(gap_tick) = gap_tick_size_percentage {
-                                    if gap_tick > 0 {
-                                        msg!("Rejecting same-bid insert due to gap tick size of {:?}", gap_tick);
-                                        return Err(AuctionError::GapBetweenBidsTooSmall.into());
-                                    }
-                                }
-
-                                msg!("Ok we can do an equivalent insert");
-                                if i == 0 {
-                                    msg!("Doing a normal insert");
-                                    bids.insert(0, bid);
-                                    break;
-                                } else {
-                                    if bids[i - 1].1 != bids[i].1 {
-                                        msg!("Doing an insert just before");
-                                        bids.insert(i, bid);
-                                        break;
-                                    }
-                                    msg!("More duplicates ahead...")
-                                }
-                            } else if i == 0 {
-                                msg!("Inserting at 0");
-                                bids.insert(0, bid);
-                                break;
-                            }
-                        }
-
-                        let max_size = BidState::max_array_size_for(*max);
-
-                        if bids.len() > max_size {
-                            bids.remove(0);
-                        }
-                        Ok(())
+            // In a capped auction, track the limited number of winners. Revealed sealed bids and
+            // uniform price bids are ranked the same way.
+            BidState::EnglishAuction { ref mut bids, max }
+            | BidState::SealedBid {
+                ref mut bids, max, ..
+            }
+            | BidState::UniformPriceAuction {
+                ref mut bids, max, ..
+            } => {
+                // Bids are kept lowest first. A new bid goes after every bid below it and before
+                // every bid of the same amount, so among equal bids the earliest placed ranks
+                // highest.
+                let index = bids.partition_point(|b| b.1 < bid.1);
+
+                if let Some(gap_tick) = gap_tick_size_percentage {
+                    if gap_tick > 0 && bids.get(index).map_or(false, |b| b.1 == bid.1) {
+                        msg!(
+                            "Rejecting same-bid insert due to gap tick size of {:?}",
+                            gap_tick
+                        );
+                        return Err(AuctionError::GapBetweenBidsTooSmall.into());
                     }
-                    _ => {
-                        msg!("Pushing bid onto stack");
-                        bids.push(bid);
-                        Ok(())
+                    if index > 0 {
+                        BidState::assert_valid_gap_insertion(gap_tick, &bids[index - 1], &bid)?;
                     }
                 }
+
+                // Once the book is full the lowest bid drops out to make room, shifting only the
+                // bids below the new one.
+                if bids.len() < bid_capacity {
+                    bids.insert(index, bid);
+                } else if index > 0 {
+                    bids[..index].rotate_left(1);
+                    bids[index - 1] = bid;
+                }
+                Ok(())
             }
 
             // In an open auction, bidding simply succeeds.
             BidState::OpenEdition { bids, max } => Ok(()),
+
+            // In a Dutch auction the first bid to reach the price takes it.
+            BidState::DutchAuction { ref mut bids, .. } => {
+                if !bids.is_empty() {
+                    return Err(AuctionError::InvalidState.into());
+                }
+                bids.push(bid);
+                *auction_state = AuctionState::Ended;
+                Ok(())
+            }
         }
     }
 
+    /// Position of a bid in the book. Bids are kept sorted by amount, so the amount is found with
+    /// a binary search and only bids of that same amount are compared by key.
+    fn position(bids: &[Bid], bid: &Bid) -> Option<usize> {
+        let start = bids.partition_point(|b| b.1 < bid.1);
+        bids[start..]
+            .iter()
+            .take_while(|b| b.1 == bid.1)
+            .position(|b| b.0 == bid.0)
+            .map(|offset| start + offset)
+    }
+
     /// Cancels a bid, if the bid was a winning bid it is removed, if the bid is invalid the
-    /// function simple no-ops.
-    pub fn cancel_bid(&mut self, key: Pubkey) -> Result<(), ProgramError> {
+    /// function simple no-ops. The bid is looked up by its key and the amount it was placed for.
+    pub fn cancel_bid(&mut self, bid: &Bid) -> Result<(), ProgramError> {
         match self {
-            BidState::EnglishAuction { ref mut bids, max } => {
-                bids.retain(|b| b.0 != key);
+            BidState::EnglishAuction { ref mut bids, .. }
+            | BidState::DutchAuction { ref mut bids, .. }
+            | BidState::UniformPriceAuction { ref mut bids, .. } => {
+                // Every bid is held under its own key, so there is at most one to remove.
+                if let Some(index) = BidState::position(bids, bid) {
+                    bids.remove(index);
+                }
                 Ok(())
             }
 
+            // Revealed sealed bids set the clearing price, so they stay put. Bids cannot be
+            // revealed and then withdrawn.
+            BidState::SealedBid { .. } => Ok(()),
+
             // In an open auction, cancelling simply succeeds. It's up to the manager of an auction
             // to decide what to do with open edition bids.
             BidState::OpenEdition { bids, max } => Ok(()),
@@ -645,7 +1482,10 @@ impl BidState {
 
     pub fn amount(&self, index: usize) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => {
+            BidState::EnglishAuction { bids, .. }
+            | BidState::DutchAuction { bids, .. }
+            | BidState::SealedBid { bids, .. }
+            | BidState::UniformPriceAuction { bids, .. } => {
                 if index >= 0 as usize && index < bids.len() {
                     return bids[bids.len() - index - 1].1;
                 } else {
@@ -662,18 +1502,16 @@ impl BidState {
 
         match self {
             // Presense in the winner list is enough to check win state.
-            BidState::EnglishAuction { bids, max } => {
-                match bids.iter().position(|bid| &bid.0 == key && bid.1 >= min) {
-                    Some(val) => {
-                        let zero_based_index = bids.len() - val - 1;
-                        if zero_based_index < *max {
-                            Some(zero_based_index)
-                        } else {
-                            None
-                        }
-                    }
-                    None => None,
-                }
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max, .. }
+            | BidState::SealedBid { bids, max, .. }
+            | BidState::UniformPriceAuction { bids, max, .. } => {
+                // Only the top of the book can hold a winner, so the losing bids below it are
+                // never looked at.
+                bids.iter()
+                    .rev()
+                    .take(*max)
+                    .position(|bid| &bid.0 == key && bid.1 >= min)
             }
             // There are no winners in an open edition, it is up to the auction manager to decide
             // what to do with open edition bids.
@@ -681,16 +1519,41 @@ impl BidState {
         }
     }
 
+    /// Same as is_winner, for a bid whose amount is known, which finds it without walking the
+    /// winners.
+    pub fn is_winning_bid(&self, bid: &Bid, min: u64) -> Option<usize> {
+        match self {
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max, .. }
+            | BidState::SealedBid { bids, max, .. }
+            | BidState::UniformPriceAuction { bids, max, .. } => {
+                let index = bids.len() - 1 - BidState::position(bids, bid)?;
+                if index < *max && bid.1 >= min {
+                    Some(index)
+                } else {
+                    None
+                }
+            }
+            BidState::OpenEdition { bids, max } => None,
+        }
+    }
+
     pub fn num_winners(&self) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => cmp::min(bids.len(), *max) as u64,
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max, .. }
+            | BidState::SealedBid { bids, max, .. }
+            | BidState::UniformPriceAuction { bids, max, .. } => cmp::min(bids.len(), *max) as u64,
             BidState::OpenEdition { bids, max } => 0,
         }
     }
 
     pub fn num_possible_winners(&self) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => *max as u64,
+            BidState::EnglishAuction { max, .. }
+            | BidState::DutchAuction { max, .. }
+            | BidState::SealedBid { max, .. }
+            | BidState::UniformPriceAuction { max, .. } => *max as u64,
             BidState::OpenEdition { bids, max } => 0,
         }
     }
@@ -698,7 +1561,10 @@ impl BidState {
     /// Idea is to present #1 winner as index 0 to outside world with this method
     pub fn winner_at(&self, index: usize) -> Option<Pubkey> {
         match self {
-            BidState::EnglishAuction { bids, max } => {
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max, .. }
+            | BidState::SealedBid { bids, max, .. }
+            | BidState::UniformPriceAuction { bids, max, .. } => {
                 if index < *max && index < bids.len() {
                     let bid = &bids[bids.len() - index - 1];
                     Some(bids[bids.len() - index - 1].0)
@@ -710,6 +1576,38 @@ impl BidState {
         }
     }
 
+    /// Price paid by each winner of a sealed-bid or uniform price auction, never below the
+    /// minimum. Sealed bids clear at the highest losing bid.
+    pub fn clearing_price(&self, minimum: u64) -> Option<u64> {
+        match self {
+            BidState::SealedBid { bids, max, .. }
+            | BidState::UniformPriceAuction {
+                bids,
+                max,
+                rule: ClearingRule::HighestLosingBid,
+            } => {
+                if bids.len() > *max {
+                    Some(cmp::max(bids[bids.len() - *max - 1].1, minimum))
+                } else {
+                    Some(minimum)
+                }
+            }
+            BidState::UniformPriceAuction {
+                bids,
+                max,
+                rule: ClearingRule::LowestWinningBid,
+            } => {
+                let winners = cmp::min(bids.len(), *max);
+                if winners > 0 {
+                    Some(cmp::max(bids[bids.len() - winners].1, minimum))
+                } else {
+                    Some(minimum)
+                }
+            }
+            _ => None,
+        }
+    }
+
     pub fn lowest_winning_bid_is_instant_bid_price(&self, instant_sale_amount: u64) -> bool {
         match self {
             // In a capped auction, track the limited number of winners.
@@ -729,12 +1627,14 @@ pub enum WinnerLimit {
     Capped(usize),
 }
 
-pub const BIDDER_METADATA_LEN: usize = 32 + 32 + 8 + 8 + 1;
+pub const BIDDER_METADATA_LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
 /// Models a set of metadata for a bidder, meant to be stored in a PDA. This allows looking up
 /// information about a bidder regardless of if they have won, lost or cancelled.
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct BidderMetadata {
+    // Account type and layout version, always Key::BidderMetadataV1.
+    pub key: Key,
     // Relationship with the bidder who's metadata this covers.
     pub bidder_pubkey: Pubkey,
     // Relationship with the auction this bid was placed on.
@@ -753,6 +1653,7 @@ impl BidderMetadata {
         if a.data_len() != BIDDER_METADATA_LEN {
             return Err(AuctionError::DataTypeMismatch.into());
         }
+        assert_key(a, Key::BidderMetadataV1)?;
 
         let bidder_meta: BidderMetadata = try_from_slice_unchecked(&a.data.borrow_mut())?;
 
@@ -760,9 +1661,139 @@ impl BidderMetadata {
     }
 }
 
+pub const BIDDER_COMMITMENT_LEN: usize = 1 + 32 + 32 + 32 + 1;
+/// Hashed bid committed to a sealed-bid auction, stored in a PDA next to the bidder metadata until
+/// the bidder reveals it.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct BidderCommitment {
+    // Account type and layout version, always Key::BidderCommitmentV1.
+    pub key: Key,
+    // Relationship with the bidder who's commitment this is.
+    pub bidder_pubkey: Pubkey,
+    // Relationship with the auction this commitment was made on.
+    pub auction_pubkey: Pubkey,
+    // hash(amount, salt) of the sealed bid.
+    pub commitment: Hash,
+    // Whether the bid has been revealed.
+    pub revealed: bool,
+}
+
+impl BidderCommitment {
+    pub fn from_account_info(a: &AccountInfo) -> Result<BidderCommitment, ProgramError> {
+        if a.data_len() != BIDDER_COMMITMENT_LEN {
+            return Err(AuctionError::DataTypeMismatch.into());
+        }
+        assert_key(a, Key::BidderCommitmentV1)?;
+
+        let commitment: BidderCommitment = try_from_slice_unchecked(&a.data.borrow_mut())?;
+
+        Ok(commitment)
+    }
+}
+
+/// Largest share of a refund the authority can offer to whoever cranks it, one percent.
+pub const MAX_CRANK_REWARD_BASIS_POINTS: u16 = 100;
+
+pub const MAX_FEE_SPLITS: usize = 5;
+pub const AUCTION_FEE_SPLITS_LEN: usize = 1 + 4 + MAX_FEE_SPLITS * (32 + 2 + 1);
+
+/// Share of each claimed bid paid to one recipient.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct FeeSplit {
+    /// Account receiving the share, a token account of the auction mint or any account on native
+    /// SOL auctions.
+    pub recipient: Pubkey,
+    /// Share of each claim in basis points.
+    pub basis_points: u16,
+    /// Whether this recipient also takes the rounding dust, exactly one split is primary.
+    pub primary: bool,
+}
+
+/// Fee splits ClaimBid distributes claimed bids across, stored in a PDA next to the auction.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct AuctionFeeSplits {
+    /// Account type and layout version, always Key::AuctionFeeSplitsV1.
+    pub key: Key,
+    /// Recipients in the order ClaimBid expects their accounts.
+    pub splits: Vec<FeeSplit>,
+}
+
+impl AuctionFeeSplits {
+    pub fn from_account_info(a: &AccountInfo) -> Result<AuctionFeeSplits, ProgramError> {
+        if a.data_len() != AUCTION_FEE_SPLITS_LEN {
+            return Err(AuctionError::DataTypeMismatch.into());
+        }
+        assert_key(a, Key::AuctionFeeSplitsV1)?;
+
+        let fee_splits: AuctionFeeSplits = try_from_slice_unchecked(&a.data.borrow_mut())?;
+
+        Ok(fee_splits)
+    }
+
+    /// Splits must have exactly one primary recipient and add up to 10,000 basis points.
+    pub fn validate(splits: &[FeeSplit]) -> ProgramResult {
+        let total: u32 = splits.iter().map(|split| split.basis_points as u32).sum();
+        let primaries = splits.iter().filter(|split| split.primary).count();
+        if splits.is_empty() || splits.len() > MAX_FEE_SPLITS || total != 10_000 || primaries != 1 {
+            return Err(AuctionError::InvalidFeeSplits.into());
+        }
+        Ok(())
+    }
+
+    /// Share of the amount owed to each recipient, rounded down, with whatever rounding leaves
+    /// over added to the primary recipient.
+    pub fn shares(&self, amount: u64) -> Result<Vec<u64>, ProgramError> {
+        let mut shares: Vec<u64> = self
+            .splits
+            .iter()
+            .map(|split| (amount as u128 * split.basis_points as u128 / 10_000) as u64)
+            .collect();
+        let primary = self
+            .splits
+            .iter()
+            .position(|split| split.primary)
+            .ok_or(AuctionError::InvalidFeeSplits)?;
+        let dust = amount
+            .checked_sub(shares.iter().sum())
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        shares[primary] = shares[primary]
+            .checked_add(dust)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        Ok(shares)
+    }
+}
+
+pub const BIDDER_POT_LEN: usize = 1 + 32 + 32 + 32 + 1 + 33;
+
+/// Key a bid is placed under, both in the bid state and as the bidder its pot and metadata are
+/// derived from. A wallets first bid is placed under the wallet itself, any others under a PDA per
+/// bid index, the wallet being recorded in the bidder metadata.
+pub fn bid_key(program_id: &Pubkey, auction: &Pubkey, bidder: &Pubkey, bid_index: u8) -> Pubkey {
+    if bid_index == 0 {
+        return *bidder;
+    }
+    let (key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction.as_ref(),
+            bidder.as_ref(),
+            BID.as_bytes(),
+            &[bid_index],
+        ],
+        program_id,
+    );
+    key
+}
+
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
 pub struct BidderPot {
+    /// Account type and layout version, always Key::BidderPotV1.
+    pub key: Key,
     /// Points at actual pot that is a token account
     pub bidder_pot: Pubkey,
     /// Originating bidder account
@@ -771,16 +1802,468 @@ pub struct BidderPot {
     pub auction_act: Pubkey,
     /// emptied or not
     pub emptied: bool,
+    /// Account that paid rent for the pot and bidder metadata, refunded when they are closed
+    pub payer: Option<Pubkey>,
 }
 
 impl BidderPot {
     pub fn from_account_info(a: &AccountInfo) -> Result<BidderPot, ProgramError> {
-        if a.data_len() != mem::size_of::<BidderPot>() {
+        if a.data_len() != BIDDER_POT_LEN {
             return Err(AuctionError::DataTypeMismatch.into());
         }
+        assert_key(a, Key::BidderPotV1)?;
 
         let bidder_pot: BidderPot = try_from_slice_unchecked(&a.data.borrow_mut())?;
 
         Ok(bidder_pot)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_program::{
+            entrypoint::SUCCESS,
+            program_stubs, system_program,
+            sysvar::{self, clock::Clock, rent::Rent, Sysvar},
+        },
+    };
+
+    fn place(state: &mut BidState, bid: &Bid, capacity: usize) {
+        state
+            .place_bid(
+                bid.clone(),
+                None,
+                None,
+                0,
+                None,
+                capacity,
+                &mut AuctionState::Started,
+            )
+            .unwrap();
+    }
+
+    fn english(max: usize) -> BidState {
+        BidState::EnglishAuction { bids: vec![], max }
+    }
+
+    pub(crate) fn extended() -> AuctionDataExtended {
+        AuctionDataExtended {
+            key: Key::AuctionExtendedV1,
+            total_uncancelled_bids: 0,
+            tick_size: None,
+            gap_tick_size_percentage: None,
+            instant_sale_price: None,
+            name: None,
+            start_auction_at: None,
+            anti_sniping: None,
+            extensions: 0,
+            total_extension: 0,
+            payer: None,
+            funded_pots: 0,
+            has_fee_splits: false,
+            allowlist_root: None,
+            max_bids_per_bidder: None,
+            paused_at: None,
+            pending_authority: None,
+            claimer: None,
+            starter: None,
+            delegated_by: None,
+            crank_reward_basis_points: 0,
+            open_bidder_pots: 0,
+        }
+    }
+
+    pub(crate) fn pda(program_id: &Pubkey, seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, program_id).0
+    }
+
+    /// Serves the rent sysvar, which processors read through Rent::get, to processor tests.
+    pub(crate) struct RentStubs;
+
+    impl program_stubs::SyscallStubs for RentStubs {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            SUCCESS
+        }
+    }
+
+    /// Account backing an AccountInfo handed to a processor in tests, rent exempt for its data.
+    pub(crate) struct TestAccount {
+        pub key: Pubkey,
+        pub owner: Pubkey,
+        pub lamports: u64,
+        pub data: Vec<u8>,
+        pub is_signer: bool,
+    }
+
+    impl TestAccount {
+        pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> TestAccount {
+            TestAccount {
+                key,
+                owner,
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                is_signer: false,
+            }
+        }
+
+        /// Account holding the value serialized at the start of `len` bytes.
+        pub fn with<T: BorshSerialize>(
+            key: Pubkey,
+            owner: Pubkey,
+            value: &T,
+            len: usize,
+        ) -> TestAccount {
+            let mut data = value.try_to_vec().unwrap();
+            data.resize(len, 0);
+            TestAccount::new(key, owner, data)
+        }
+
+        pub fn signer(key: Pubkey) -> TestAccount {
+            TestAccount {
+                is_signer: true,
+                ..TestAccount::new(key, system_program::id(), vec![])
+            }
+        }
+
+        /// Clock sysvar at the given time.
+        pub fn clock(unix_timestamp: UnixTimestamp) -> TestAccount {
+            let mut account =
+                TestAccount::new(sysvar::clock::id(), sysvar::id(), vec![0; Clock::size_of()]);
+            Clock {
+                unix_timestamp,
+                ..Clock::default()
+            }
+            .to_account_info(&mut account.info())
+            .unwrap();
+            account
+        }
+
+        pub fn info(&mut self) -> AccountInfo {
+            AccountInfo::new(
+                &self.key,
+                self.is_signer,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    pub(crate) fn auction(bid_state: BidState) -> AuctionData {
+        AuctionData {
+            key: Key::AuctionV1,
+            authority: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            last_bid: None,
+            ended_at: None,
+            end_auction_at: None,
+            end_auction_gap: None,
+            price_floor: PriceFloor::None([0; 32]),
+            state: AuctionState::Started,
+            bid_state,
+        }
+    }
+
+    #[test]
+    fn earliest_of_equal_bids_wins() {
+        let mut state = english(2);
+        let first = Bid(Pubkey::new_unique(), 100);
+        let second = Bid(Pubkey::new_unique(), 100);
+        let lower = Bid(Pubkey::new_unique(), 50);
+        for bid in &[lower.clone(), first.clone(), second.clone()] {
+            place(&mut state, bid, 4);
+        }
+
+        assert_eq!(state.winner_at(0), Some(first.0));
+        assert_eq!(state.winner_at(1), Some(second.0));
+        assert_eq!(state.is_winner(&lower.0, 0), None);
+        assert_eq!(state.is_winning_bid(&first, 0), Some(0));
+        assert_eq!(state.is_winning_bid(&second, 0), Some(1));
+        assert_eq!(state.is_winning_bid(&lower, 0), None);
+    }
+
+    #[test]
+    fn full_book_evicts_the_lowest_bid() {
+        let mut state = english(2);
+        let bids: Vec<Bid> = [30, 10, 20]
+            .iter()
+            .map(|amount| Bid(Pubkey::new_unique(), *amount))
+            .collect();
+        for bid in &bids {
+            place(&mut state, bid, 3);
+        }
+
+        // The book holds three bids, a fourth above the lowest pushes it out.
+        let newcomer = Bid(Pubkey::new_unique(), 15);
+        place(&mut state, &newcomer, 3);
+        match &state {
+            BidState::EnglishAuction { bids: book, .. } => {
+                assert_eq!(
+                    *book,
+                    vec![newcomer.clone(), bids[2].clone(), bids[0].clone()]
+                )
+            }
+            _ => unreachable!(),
+        }
+
+        // A bid below everything in a full book is dropped.
+        let lowest = Bid(Pubkey::new_unique(), 5);
+        place(&mut state, &lowest, 3);
+        assert_eq!(state.is_winner(&lowest.0, 0), None);
+        assert_eq!(state.winner_at(0), Some(bids[0].0));
+    }
+
+    #[test]
+    fn cancel_removes_only_the_matching_bid() {
+        let mut state = english(3);
+        let first = Bid(Pubkey::new_unique(), 100);
+        let second = Bid(Pubkey::new_unique(), 100);
+        place(&mut state, &first, 6);
+        place(&mut state, &second, 6);
+
+        // The wrong amount finds nothing.
+        state.cancel_bid(&Bid(first.0, 90)).unwrap();
+        assert_eq!(state.num_winners(), 2);
+
+        state.cancel_bid(&first).unwrap();
+        assert_eq!(state.num_winners(), 1);
+        assert_eq!(state.winner_at(0), Some(second.0));
+    }
+
+    #[test]
+    fn dutch_price_falls_over_the_window() {
+        let mut linear = auction(BidState::new_dutch(1_000, 100, PriceDecay::Linear));
+        // Not started, the price has not begun to fall.
+        assert_eq!(linear.dutch_price(5_000).unwrap(), Some(1_000));
+
+        // Started at 1,000 with a window of 100 seconds.
+        linear.ended_at = Some(1_100);
+        linear.end_auction_at = Some(100);
+        assert_eq!(linear.dutch_price(900).unwrap(), Some(1_000));
+        assert_eq!(linear.dutch_price(1_000).unwrap(), Some(1_000));
+        assert_eq!(linear.dutch_price(1_050).unwrap(), Some(550));
+        assert_eq!(linear.dutch_price(1_100).unwrap(), Some(100));
+        assert_eq!(linear.dutch_price(2_000).unwrap(), Some(100));
+
+        let mut stepped = auction(BidState::new_dutch(1_000, 100, PriceDecay::Stepped(30)));
+        stepped.ended_at = Some(1_100);
+        stepped.end_auction_at = Some(100);
+        assert_eq!(stepped.dutch_price(1_029).unwrap(), Some(1_000));
+        assert_eq!(stepped.dutch_price(1_050).unwrap(), Some(730));
+        assert_eq!(stepped.dutch_price(1_099).unwrap(), Some(190));
+        assert_eq!(stepped.dutch_price(1_100).unwrap(), Some(100));
+
+        assert_eq!(auction(english(1)).dutch_price(1_050).unwrap(), None);
+    }
+
+    fn split(basis_points: u16, primary: bool) -> FeeSplit {
+        FeeSplit {
+            recipient: Pubkey::new_unique(),
+            basis_points,
+            primary,
+        }
+    }
+
+    #[test]
+    fn fee_splits_need_one_primary_and_the_whole_amount() {
+        assert!(AuctionFeeSplits::validate(&[split(7_000, false), split(3_000, true)]).is_ok());
+
+        let invalid = [
+            vec![],
+            vec![split(7_000, true), split(2_000, false)],
+            vec![split(7_000, true), split(3_000, true)],
+            vec![split(7_000, false), split(3_000, false)],
+            vec![split(2_000, true); MAX_FEE_SPLITS + 1],
+        ];
+        for splits in &invalid {
+            assert_eq!(
+                AuctionFeeSplits::validate(splits),
+                Err(AuctionError::InvalidFeeSplits.into())
+            );
+        }
+    }
+
+    #[test]
+    fn fee_split_dust_goes_to_the_primary() {
+        let fee_splits = AuctionFeeSplits {
+            key: Key::AuctionFeeSplitsV1,
+            splits: vec![split(7_000, false), split(3_000, true)],
+        };
+        assert_eq!(fee_splits.shares(1_001).unwrap(), vec![700, 301]);
+        assert_eq!(fee_splits.shares(0).unwrap(), vec![0, 0]);
+        assert_eq!(
+            fee_splits.shares(u64::MAX).unwrap().iter().sum::<u64>(),
+            u64::MAX
+        );
+    }
+
+    /// Book holding a bid of each amount, placed in order.
+    fn book(mut state: BidState, amounts: &[u64]) -> BidState {
+        for amount in amounts {
+            place(&mut state, &Bid(Pubkey::new_unique(), *amount), 8);
+        }
+        state
+    }
+
+    #[test]
+    fn sealed_bids_clear_at_the_highest_losing_bid() {
+        let sealed = book(BidState::new_sealed_bid(2, 60, 10), &[20, 50, 10, 30]);
+        assert_eq!(sealed.clearing_price(0), Some(20));
+        // Never below the price floor.
+        assert_eq!(sealed.clearing_price(25), Some(25));
+
+        // Every revealed bid won, so the winners pay the floor.
+        let undersubscribed = book(BidState::new_sealed_bid(2, 60, 10), &[50, 30]);
+        assert_eq!(undersubscribed.clearing_price(15), Some(15));
+        assert_eq!(
+            book(BidState::new_sealed_bid(2, 60, 10), &[]).clearing_price(0),
+            Some(0)
+        );
+
+        // Winners of other auctions pay their own bid.
+        assert_eq!(book(english(2), &[50, 30]).clearing_price(0), None);
+    }
+
+    #[test]
+    fn sealed_bids_are_revealed_after_the_commit_phase() {
+        let mut sealed = auction(BidState::new_sealed_bid(2, 60, 10));
+        sealed.ended_at = Some(100);
+
+        assert!(sealed.in_commit_phase(100));
+        assert!(!sealed.in_reveal_phase(100).unwrap());
+        assert!(!sealed.in_commit_phase(101));
+        assert!(sealed.in_reveal_phase(101).unwrap());
+        assert!(sealed.in_reveal_phase(160).unwrap());
+        assert!(!sealed.ended(160).unwrap());
+
+        assert!(!sealed.in_reveal_phase(161).unwrap());
+        assert!(sealed.ended(161).unwrap());
+    }
+
+    #[test]
+    fn uniform_price_clears_by_its_rule() {
+        let amounts = [20, 50, 10, 30];
+        let lowest_winning = book(
+            BidState::new_uniform_price(2, ClearingRule::LowestWinningBid),
+            &amounts,
+        );
+        assert_eq!(lowest_winning.clearing_price(0), Some(30));
+        assert_eq!(lowest_winning.clearing_price(40), Some(40));
+        let highest_losing = book(
+            BidState::new_uniform_price(2, ClearingRule::HighestLosingBid),
+            &amounts,
+        );
+        assert_eq!(highest_losing.clearing_price(0), Some(20));
+        assert_eq!(highest_losing.clearing_price(25), Some(25));
+
+        // Every bid won, the lowest of them still sets the price under the first rule but nothing
+        // lost under the second so the winners pay the floor.
+        let lowest_winning = book(
+            BidState::new_uniform_price(3, ClearingRule::LowestWinningBid),
+            &[50, 30],
+        );
+        assert_eq!(lowest_winning.clearing_price(15), Some(30));
+        let highest_losing = book(
+            BidState::new_uniform_price(3, ClearingRule::HighestLosingBid),
+            &[50, 30],
+        );
+        assert_eq!(highest_losing.clearing_price(15), Some(15));
+
+        // The auction clears at its minimum price.
+        let mut uniform = auction(lowest_winning);
+        assert_eq!(uniform.clearing_price(), Some(30));
+        uniform.price_floor = PriceFloor::MinimumPrice([45, 0, 0, 0]);
+        assert_eq!(uniform.clearing_price(), Some(45));
+    }
+
+    #[test]
+    fn late_bids_extend_within_the_policy() {
+        let mut late = auction(english(1));
+        late.ended_at = Some(1_000);
+        let mut ext = AuctionDataExtended {
+            anti_sniping: Some(AntiSnipingPolicy {
+                window: 60,
+                extension: 50,
+                max_total_extension: 120,
+                max_extensions: 3,
+            }),
+            ..extended()
+        };
+
+        // Outside the window nothing moves.
+        assert!(!late.extend_for_late_bid(940, &mut ext).unwrap());
+        assert_eq!(late.ended_at, Some(1_000));
+
+        assert!(late.extend_for_late_bid(941, &mut ext).unwrap());
+        assert_eq!(late.ended_at, Some(1_050));
+        assert!(late.extend_for_late_bid(1_050, &mut ext).unwrap());
+        assert_eq!(late.ended_at, Some(1_100));
+        // Only what is left of the total extension is added.
+        assert!(late.extend_for_late_bid(1_090, &mut ext).unwrap());
+        assert_eq!(late.ended_at, Some(1_120));
+        assert_eq!((ext.extensions, ext.total_extension), (3, 120));
+
+        // Once the total is spent, or the extensions run out, late bids no longer extend.
+        ext.extensions = 2;
+        assert!(!late.extend_for_late_bid(1_110, &mut ext).unwrap());
+        ext.total_extension = 0;
+        ext.extensions = 3;
+        assert!(!late.extend_for_late_bid(1_110, &mut ext).unwrap());
+        assert_eq!(late.ended_at, Some(1_120));
+
+        // Nor does a bid after the end time or one that ended the auction.
+        ext.extensions = 0;
+        assert!(!late.extend_for_late_bid(1_121, &mut ext).unwrap());
+        late.state = AuctionState::Ended;
+        assert!(!late.extend_for_late_bid(1_110, &mut ext).unwrap());
+
+        // Auctions without a policy or an end time are never extended.
+        late.state = AuctionState::Started;
+        assert!(!late.extend_for_late_bid(1_110, &mut extended()).unwrap());
+        late.ended_at = None;
+        assert!(!late.extend_for_late_bid(1_110, &mut ext).unwrap());
+        assert_eq!((ext.extensions, ext.total_extension), (0, 0));
+    }
+
+    #[test]
+    fn resume_gives_back_the_time_spent_paused() {
+        let mut paused = auction(english(1));
+        paused.ended_at = Some(1_000);
+        paused.end_auction_gap = Some(100);
+        paused.last_bid = Some(500);
+        let mut ext = extended();
+
+        // Only a started auction is resumed.
+        assert_eq!(
+            paused.resume(700, &mut ext),
+            Err(AuctionError::AuctionTransitionInvalid.into())
+        );
+
+        paused.pause(600, &mut ext).unwrap();
+        assert_eq!(paused.state, AuctionState::Paused);
+        assert_eq!(ext.paused_at, Some(600));
+        assert_eq!(
+            paused.pause(650, &mut ext),
+            Err(AuctionError::AuctionTransitionInvalid.into())
+        );
+
+        paused.resume(900, &mut ext).unwrap();
+        assert_eq!(paused.state, AuctionState::Started);
+        assert_eq!(ext.paused_at, None);
+        assert_eq!(paused.ended_at, Some(1_300));
+        assert_eq!(paused.last_bid, Some(800));
+        assert!(!paused.ended(900).unwrap());
+
+        // An auction over by its end time cannot be paused.
+        assert_eq!(
+            paused.pause(1_301, &mut ext),
+            Err(AuctionError::InvalidState.into())
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Second half of handing an auction to a new authority, the key proposed with ProposeAuthority
+//! signs to take over. Roles delegated by the old authority are dropped.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended},
+    utils::{assert_derivation, assert_owned_by, assert_signer},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct AcceptAuthorityArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    new_authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        new_authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.new_authority)?;
+    Ok(accounts)
+}
+
+pub fn accept_authority<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: AcceptAuthorityArgs,
+) -> ProgramResult {
+    msg!("+ Processing AcceptAuthority");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Only the key proposed by the current authority can take over.
+    let pending_authority = auction_extended
+        .delegate(&auction.authority, auction_extended.pending_authority)
+        .ok_or(AuctionError::NoPendingAuthority)?;
+    if pending_authority != *accounts.new_authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    auction.authority = pending_authority;
+    auction_extended.delegate_for(&pending_authority);
+
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Aborts an auction so that nobody wins it. Unlike EndAuction, which leaves the top bids to be
+//! claimed, a cancelled auction has no winners, ClaimBid is refused and every bidder can take
+//! their bid back with CancelBid, sealed bidders getting their deposit back too. An auction that is
+//! already over, or holds a bid at the instant sale price, cannot be cancelled.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended},
+    utils::{assert_derivation, assert_owned_by, assert_signer},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CancelAuctionArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.authority)?;
+    Ok(accounts)
+}
+
+pub fn cancel_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: CancelAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing CancelAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Check authority is correct.
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    auction.cancel(clock.unix_timestamp, auction_extended.instant_sale_price)?;
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
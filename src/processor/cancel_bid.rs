@@ -3,13 +3,25 @@
 //! 1) The auction is still going on, in which case it is possible to cancel a bid at any time.
 //! 2) The auction has finished, but the bid did not win. This allows users to claim back their
 //!    funds from bid accounts.
+//!
+//! Every bid can be withdrawn from a cancelled auction, winning or not.
+//!
+//! Sealed bids cannot be withdrawn while bids are being revealed. Once a sealed-bid auction is
+//! over losing bidders may withdraw, less the deposit if they never revealed their bid. Winners of
+//! auctions with a clearing price withdraw the rest of their bid through RefundExcess.
+//!
+//! Bids a wallet holds beyond its first are cancelled by passing their bid key as the bidder and
+//! the wallet as the bid owner.
 
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionDataExtended, BidderMetadata, BidderPot},
+    processor::{
+        AuctionData, AuctionDataExtended, Bid, BidState, BidderCommitment, BidderMetadata,
+        BidderPot,
+    },
     utils::{
-        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
-        assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
+        assert_bid_owner, assert_derivation, assert_owned_by, assert_token_program_matches_package,
+        create_or_allocate_account_raw, native_pot_withdraw, pot_balance, spl_token_transfer,
         TokenTransferParams,
     },
     EXTENDED, PREFIX,
@@ -25,12 +37,10 @@ use {
         msg,
         program::invoke_signed,
         program_error::ProgramError,
-        program_pack::Pack,
         pubkey::Pubkey,
         system_instruction,
         sysvar::{clock::Clock, Sysvar},
     },
-    spl_token::state::Account,
 };
 
 #[repr(C)]
@@ -52,6 +62,8 @@ struct Accounts<'a, 'b: 'a> {
     rent: &'a AccountInfo<'b>,
     system: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
+    bidder_commitment: Option<&'a AccountInfo<'b>>,
+    bid_owner: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -72,19 +84,21 @@ fn parse_accounts<'a, 'b: 'a>(
         rent: next_account_info(account_iter)?,
         system: next_account_info(account_iter)?,
         token_program: next_account_info(account_iter)?,
+        bidder_commitment: next_account_info(account_iter).ok(),
+        bid_owner: next_account_info(account_iter).ok(),
     };
 
     assert_owned_by(accounts.auction, program_id)?;
     assert_owned_by(accounts.auction_extended, program_id)?;
     assert_owned_by(accounts.bidder_meta, program_id)?;
-    assert_owned_by(accounts.mint, &spl_token::id())?;
+    assert_owned_by(accounts.mint, accounts.token_program.key)?;
     assert_owned_by(accounts.bidder_pot, program_id)?;
-    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
-    assert_signer(accounts.bidder)?;
     assert_token_program_matches_package(accounts.token_program)?;
 
-    if *accounts.token_program.key != spl_token::id() {
-        return Err(AuctionError::InvalidTokenProgram.into());
+    if let Some(bidder_commitment) = accounts.bidder_commitment {
+        if !bidder_commitment.data_is_empty() {
+            assert_owned_by(bidder_commitment, program_id)?;
+        }
     }
 
     Ok(accounts)
@@ -98,12 +112,6 @@ pub fn cancel_bid(
     msg!("+ Processing Cancelbid");
     let accounts = parse_accounts(program_id, accounts)?;
 
-    // The account within the pot must be owned by us.
-    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
-    if actual_account.owner != *accounts.auction.key {
-        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
-    }
-
     // Derive and load Auction.
     let auction_bump = assert_derivation(
         program_id,
@@ -131,6 +139,16 @@ pub fn cancel_bid(
 
     // Load auction extended account to check instant_sale_price
     // and update cancelled bids if auction still active
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
     let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
 
     // Load the clock, used for various auction timing.
@@ -154,6 +172,8 @@ pub fn cancel_bid(
     if accounts.bidder_meta.owner != program_id {
         return Err(AuctionError::MetadataInvalid.into());
     }
+    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+    assert_bid_owner(accounts.bidder, accounts.bid_owner, &metadata.bidder_pubkey)?;
 
     // Derive Pot address, this account wraps/holds an SPL account to transfer tokens out of.
     let pot_seeds = [
@@ -179,8 +199,10 @@ pub fn cancel_bid(
     }
 
     // Refuse to cancel if the auction ended and this person is a winning account.
-    let winner_bid_index = auction.is_winner(accounts.bidder.key);
-    if auction.ended(clock.unix_timestamp)? && winner_bid_index.is_some() {
+    let ended = auction.ended(clock.unix_timestamp)?;
+    let bid = Bid(*accounts.bidder.key, metadata.last_bid);
+    let winner_bid_index = auction.is_winning_bid(&bid);
+    if ended && winner_bid_index.is_some() {
         return Err(AuctionError::InvalidState.into());
     }
 
@@ -199,19 +221,68 @@ pub fn cancel_bid(
         return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
     }
 
-    // Transfer SPL bid balance back to the user.
-    let account: Account = Account::unpack_from_slice(&accounts.bidder_pot_token.data.borrow())?;
-    spl_token_transfer(TokenTransferParams {
-        source: accounts.bidder_pot_token.clone(),
-        destination: accounts.bidder_token.clone(),
-        authority: accounts.auction.clone(),
-        authority_signer_seeds: auction_seeds,
-        token_program: accounts.token_program.clone(),
-        amount: account.amount,
-    })?;
+    let native = auction.is_native();
+    let mut refund = pot_balance(
+        native,
+        accounts.auction,
+        accounts.bidder_pot,
+        accounts.bidder_pot_token,
+    )?;
+    let mut deposit_held = false;
+
+    // Sealed bids are locked during the reveal phase, afterwards hold back the deposit of a bid
+    // that was never revealed unless the authority has already claimed it.
+    if let BidState::SealedBid { deposit, .. } = auction.bid_state {
+        if auction.in_reveal_phase(clock.unix_timestamp)? {
+            return Err(AuctionError::InvalidSealedBidPhase.into());
+        }
+
+        let bidder_commitment = accounts
+            .bidder_commitment
+            .ok_or(AuctionError::InvalidBidAccount)?;
+        assert_derivation(
+            program_id,
+            bidder_commitment,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                accounts.bidder.key.as_ref(),
+                "commitment".as_bytes(),
+            ],
+        )?;
+        let mut commitment = BidderCommitment::from_account_info(bidder_commitment)?;
+
+        if !ended {
+            // A commitment withdrawn before bidding closes owes no deposit.
+            commitment.revealed = true;
+            commitment.serialize(&mut *bidder_commitment.data.borrow_mut())?;
+        } else if deposit > 0
+            && !bidder_pot.emptied
+            && !commitment.revealed
+            && auction.state != AuctionState::Cancelled
+        {
+            refund = refund.saturating_sub(deposit);
+            deposit_held = true;
+        }
+    }
+
+    // Transfer bid balance back to the user.
+    if native {
+        native_pot_withdraw(accounts.bidder_pot, accounts.bidder_token, refund)?;
+    } else {
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_pot_token.clone(),
+            destination: accounts.bidder_token.clone(),
+            mint: accounts.mint.clone(),
+            authority: accounts.auction.clone(),
+            authority_signer_seeds: auction_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: refund,
+        })?;
+    }
 
     // Update Metadata
-    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
     let already_cancelled = metadata.cancelled;
     BidderMetadata {
         cancelled: true,
@@ -219,21 +290,15 @@ pub fn cancel_bid(
     }
     .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
 
+    // The pot is settled now unless the authority still has a forfeited deposit to claim from it.
+    if !already_cancelled && !deposit_held {
+        auction_extended.pot_drained();
+    }
+
     // Update Auction
 
-    if auction.state != AuctionState::Ended {
+    if auction.state != AuctionState::Ended && auction.state != AuctionState::Cancelled {
         // Once ended we want uncancelled bids to retain it's pre-ending count
-        assert_derivation(
-            program_id,
-            accounts.auction_extended,
-            &[
-                PREFIX.as_bytes(),
-                program_id.as_ref(),
-                args.resource.as_ref(),
-                EXTENDED.as_bytes(),
-            ],
-        )?;
-
         msg!("Already cancelled is {:?}", already_cancelled);
 
         if !already_cancelled && auction_extended.total_uncancelled_bids > 0 {
@@ -242,12 +307,15 @@ pub fn cancel_bid(
                 .checked_sub(1)
                 .ok_or(AuctionError::NumericalOverflowError)?;
         }
-        auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
-        // Only cancel the bid if the auction has not ended yet
-        auction.bid_state.cancel_bid(*accounts.bidder.key);
+        // Only cancel the bid if the auction has not ended yet, losing bids that set a clearing
+        // price have to stay in place once bidding is over.
+        if !ended || auction.clearing_price().is_none() {
+            auction.bid_state.cancel_bid(&bid)?;
+        }
         auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
     }
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
     Ok(())
 }
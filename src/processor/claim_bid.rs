@@ -1,15 +1,26 @@
-//! Claim bid winnings into a target SPL account, only the authorised key can do this, though the
-//! target can be any SPL account.
+//! Claim bid winnings into a target SPL account, only the authorised key or the claimer it delegated
+//! to can do this, though the target can be any SPL account. Winnings of a native SOL auction are paid out in lamports to any
+//! target account.
+//!
+//! On sealed-bid and uniform price auctions the winners only pay the clearing price, the rest of
+//! the pot is left for them to take back with RefundExcess. The deposit of a sealed bidder that
+//! never revealed can be claimed in the same way as a winning bid.
+//!
+//! Auctions created with fee splits pay every claim out to the stored recipients instead of the
+//! destination, each taking its share in basis points and the primary recipient the rounding dust.
 
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionDataExtended, BidderMetadata, BidderPot},
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionFeeSplits, AuctionState, BidState,
+        BidderCommitment, BidderMetadata, BidderPot,
+    },
     utils::{
-        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
-        assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
-        TokenTransferParams,
+        assert_authority_or_role, assert_derivation, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, create_or_allocate_account_raw, native_pot_withdraw,
+        pot_balance, spl_token_transfer, TokenTransferParams,
     },
-    EXTENDED, PREFIX,
+    EXTENDED, FEE_SPLITS, PREFIX,
 };
 
 use {
@@ -25,7 +36,7 @@ use {
         system_instruction,
         sysvar::{clock::Clock, Sysvar},
     },
-    spl_token::state::Account,
+    std::cmp,
 };
 
 #[repr(C)]
@@ -44,7 +55,11 @@ struct Accounts<'a, 'b: 'a> {
     mint: &'a AccountInfo<'b>,
     clock_sysvar: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
-    auction_extended: Option<&'a AccountInfo<'b>>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_commitment: Option<&'a AccountInfo<'b>>,
+    bidder_meta: Option<&'a AccountInfo<'b>>,
+    fee_splits: Option<&'a AccountInfo<'b>>,
+    recipients: &'a [AccountInfo<'b>],
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -62,23 +77,34 @@ fn parse_accounts<'a, 'b: 'a>(
         mint: next_account_info(account_iter)?,
         clock_sysvar: next_account_info(account_iter)?,
         token_program: next_account_info(account_iter)?,
-        auction_extended: next_account_info(account_iter).ok(),
+        auction_extended: next_account_info(account_iter)?,
+        bidder_commitment: next_account_info(account_iter).ok(),
+        bidder_meta: next_account_info(account_iter).ok(),
+        fee_splits: next_account_info(account_iter).ok(),
+        recipients: account_iter.as_slice(),
     };
 
     assert_owned_by(accounts.auction, program_id)?;
-    assert_owned_by(accounts.mint, &spl_token::id())?;
-    assert_owned_by(accounts.destination, &spl_token::id())?;
-    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+    assert_owned_by(accounts.mint, accounts.token_program.key)?;
     assert_owned_by(accounts.bidder_pot, program_id)?;
     assert_signer(accounts.authority)?;
     assert_token_program_matches_package(accounts.token_program)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
 
-    if let Some(auction_extended) = accounts.auction_extended {
-        assert_owned_by(auction_extended, program_id)?;
+    if let Some(bidder_commitment) = accounts.bidder_commitment {
+        if !bidder_commitment.data_is_empty() {
+            assert_owned_by(bidder_commitment, program_id)?;
+        }
     }
 
-    if *accounts.token_program.key != spl_token::id() {
-        return Err(AuctionError::InvalidTokenProgram.into());
+    if let Some(bidder_meta) = accounts.bidder_meta {
+        assert_owned_by(bidder_meta, program_id)?;
+    }
+
+    if let Some(fee_splits) = accounts.fee_splits {
+        if !fee_splits.data_is_empty() {
+            assert_owned_by(fee_splits, program_id)?;
+        }
     }
 
     Ok(accounts)
@@ -93,12 +119,6 @@ pub fn claim_bid(
     let accounts = parse_accounts(program_id, accounts)?;
     let clock = Clock::from_account_info(accounts.clock_sysvar)?;
 
-    // The account within the pot must be owned by us.
-    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
-    if actual_account.owner != *accounts.auction.key {
-        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
-    }
-
     // Derive and load Auction.
     let auction_bump = assert_derivation(
         program_id,
@@ -120,39 +140,69 @@ pub fn claim_bid(
     // Load the auction and verify this bid is valid.
     let auction = AuctionData::from_account_info(accounts.auction)?;
 
-    if auction.authority != *accounts.authority.key {
-        return Err(AuctionError::InvalidAuthority.into());
+    // A cancelled auction has no winnings to claim, every bidder takes their funds back instead.
+    if auction.state == AuctionState::Cancelled {
+        return Err(AuctionError::AuctionCancelled.into());
     }
 
     // User must have won the auction in order to claim their funds. Check early as the rest of the
     // checks will be for nothing otherwise.
     let bid_index = auction.is_winner(accounts.bidder.key);
-    if bid_index.is_none() {
+
+    // A sealed bid that was never revealed forfeits its deposit instead.
+    let forfeited_deposit = match (&auction.bid_state, bid_index) {
+        (BidState::SealedBid { deposit, .. }, None) => {
+            let bidder_commitment = accounts
+                .bidder_commitment
+                .ok_or(AuctionError::InvalidBidAccount)?;
+            assert_derivation(
+                program_id,
+                bidder_commitment,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    accounts.auction.key.as_ref(),
+                    accounts.bidder.key.as_ref(),
+                    "commitment".as_bytes(),
+                ],
+            )?;
+            if BidderCommitment::from_account_info(bidder_commitment)?.revealed || *deposit == 0 {
+                None
+            } else {
+                Some(*deposit)
+            }
+        }
+        _ => None,
+    };
+
+    if bid_index.is_none() && forfeited_deposit.is_none() {
         msg!("User {:?} is not winner", accounts.bidder.key);
         return Err(AuctionError::InvalidState.into());
     }
 
-    let instant_sale_price = accounts.auction_extended.and_then(|info| {
-        assert_derivation(
-            program_id,
-            info,
-            &[
-                PREFIX.as_bytes(),
-                program_id.as_ref(),
-                args.resource.as_ref(),
-                EXTENDED.as_bytes(),
-            ],
-        )
-        .ok()?;
+    // The extended data is always required, as it records whether the claim must be split.
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
 
-        AuctionDataExtended::from_account_info(info)
-            .ok()?
-            .instant_sale_price
-    });
+    // Check authority is correct, or the claimer it delegated to.
+    assert_authority_or_role(
+        &auction.authority,
+        auction_extended.delegate(&auction.authority, auction_extended.claimer),
+        accounts.authority.key,
+    )?;
 
     // Auction either must have ended or bidder pay instant_sale_price
     if !auction.ended(clock.unix_timestamp)? {
-        match instant_sale_price {
+        match auction_extended.instant_sale_price {
             Some(instant_sale_price)
                 if auction.bid_state.amount(bid_index.unwrap()) < instant_sale_price =>
             {
@@ -168,6 +218,14 @@ pub fn claim_bid(
         return Err(AuctionError::IncorrectMint.into());
     }
 
+    let native = auction.is_native();
+    let balance = pot_balance(
+        native,
+        accounts.auction,
+        accounts.bidder_pot,
+        accounts.bidder_pot_token,
+    )?;
+
     // Derive Pot address, this account wraps/holds an SPL account to transfer tokens into.
     let pot_seeds = [
         PREFIX.as_bytes(),
@@ -197,18 +255,258 @@ pub fn claim_bid(
         return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
     }
 
-    // Transfer SPL bid balance back to the user.
-    spl_token_transfer(TokenTransferParams {
-        source: accounts.bidder_pot_token.clone(),
-        destination: accounts.destination.clone(),
-        authority: accounts.auction.clone(),
-        authority_signer_seeds: auction_seeds,
-        token_program: accounts.token_program.clone(),
-        amount: actual_account.amount,
-    })?;
+    // Sealed-bid pots are shared with the bidder, so they can only be claimed from once.
+    let clearing_price = auction.clearing_price();
+    if clearing_price.is_some() && bidder_pot.emptied {
+        return Err(AuctionError::BidderPotEmpty.into());
+    }
+
+    // The pot is settled once both sides have taken their share. When the bidder is still owed the
+    // rest of the pot, through RefundExcess or CancelBid, that instruction records it instead.
+    let bidder_settled = if forfeited_deposit.is_some() || clearing_price.is_some() {
+        let bidder_meta = accounts.bidder_meta.ok_or(AuctionError::MetadataInvalid)?;
+        assert_derivation(
+            program_id,
+            bidder_meta,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                accounts.bidder.key.as_ref(),
+                "metadata".as_bytes(),
+            ],
+        )?;
+        BidderMetadata::from_account_info(bidder_meta)?.cancelled
+    } else {
+        true
+    };
+
+    let amount = match (forfeited_deposit, clearing_price) {
+        (Some(deposit), _) => cmp::min(deposit, balance),
+        (None, Some(price)) => price,
+        (None, None) => balance,
+    };
+
+    // Work out who is paid what, the recipients must be given in the order the splits store them.
+    let payouts: Vec<(&AccountInfo, u64)> = if auction_extended.has_fee_splits {
+        let fee_splits_info = accounts.fee_splits.ok_or(AuctionError::InvalidFeeSplits)?;
+        assert_derivation(
+            program_id,
+            fee_splits_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                args.resource.as_ref(),
+                FEE_SPLITS.as_bytes(),
+            ],
+        )?;
+        let fee_splits = AuctionFeeSplits::from_account_info(fee_splits_info)?;
+        if fee_splits.splits.len() != accounts.recipients.len()
+            || fee_splits
+                .splits
+                .iter()
+                .zip(accounts.recipients.iter())
+                .any(|(split, recipient)| split.recipient != *recipient.key)
+        {
+            return Err(AuctionError::FeeSplitRecipientMismatch.into());
+        }
+        accounts
+            .recipients
+            .iter()
+            .zip(fee_splits.shares(amount)?)
+            .collect()
+    } else {
+        vec![(accounts.destination, amount)]
+    };
+
+    // Transfer bid balance out of the pot. Native SOL winnings can be paid to any account, other
+    // winnings only to an SPL account.
+    for (destination, share) in payouts {
+        if share == 0 {
+            continue;
+        }
+        if native {
+            native_pot_withdraw(accounts.bidder_pot, destination, share)?;
+        } else {
+            assert_owned_by(destination, accounts.token_program.key)?;
+            spl_token_transfer(TokenTransferParams {
+                source: accounts.bidder_pot_token.clone(),
+                destination: destination.clone(),
+                mint: accounts.mint.clone(),
+                authority: accounts.auction.clone(),
+                authority_signer_seeds: auction_seeds,
+                token_program: accounts.token_program.clone(),
+                amount: share,
+            })?;
+        }
+    }
+
+    if !bidder_pot.emptied && bidder_settled {
+        auction_extended.pot_drained();
+        auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+    }
 
     bidder_pot.emptied = true;
     bidder_pot.serialize(&mut *accounts.bidder_pot.data.borrow_mut())?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::processor::{
+            tests::{self, extended, pda, RentStubs, TestAccount},
+            Bid, FeeSplit, Key, AUCTION_FEE_SPLITS_LEN, BASE_AUCTION_DATA_SIZE, BIDDER_POT_LEN,
+            MAX_AUCTION_DATA_EXTENDED_SIZE,
+        },
+        solana_program::{program_stubs, system_program},
+        std::mem,
+    };
+
+    const BID: u64 = 1_000_000_000;
+
+    /// Accounts of a claim on an ended native SOL auction with a 70/30 fee split, in ClaimBid
+    /// order, the pot given as both the pot and its token account.
+    fn claim_accounts(program_id: &Pubkey, resource: &Pubkey) -> Vec<TestAccount> {
+        let authority = Pubkey::new_unique();
+        let bidder = Pubkey::new_unique();
+        let recipients = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let auction = pda(
+            program_id,
+            &[PREFIX.as_bytes(), program_id.as_ref(), resource.as_ref()],
+        );
+        let pot = pda(
+            program_id,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                auction.as_ref(),
+                bidder.as_ref(),
+            ],
+        );
+
+        let auction_data = AuctionData {
+            authority,
+            token_mint: spl_token::native_mint::id(),
+            last_bid: Some(0),
+            ended_at: Some(0),
+            state: AuctionState::Ended,
+            ..tests::auction(BidState::EnglishAuction {
+                bids: vec![Bid(bidder, BID)],
+                max: 1,
+            })
+        };
+        let extended = AuctionDataExtended {
+            total_uncancelled_bids: 1,
+            funded_pots: 1,
+            has_fee_splits: true,
+            open_bidder_pots: 1,
+            ..extended()
+        };
+        let fee_splits = AuctionFeeSplits {
+            key: Key::AuctionFeeSplitsV1,
+            splits: vec![
+                FeeSplit {
+                    recipient: recipients[0],
+                    basis_points: 7_000,
+                    primary: true,
+                },
+                FeeSplit {
+                    recipient: recipients[1],
+                    basis_points: 3_000,
+                    primary: false,
+                },
+            ],
+        };
+        let bidder_pot = BidderPot {
+            key: Key::BidderPotV1,
+            bidder_pot: pot,
+            bidder_act: bidder,
+            auction_act: auction,
+            emptied: false,
+            payer: None,
+        };
+
+        let mut pot_account = TestAccount::with(pot, *program_id, &bidder_pot, BIDDER_POT_LEN);
+        pot_account.lamports += BID;
+        vec![
+            TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]),
+            pot_account,
+            TestAccount::signer(authority),
+            TestAccount::with(
+                auction,
+                *program_id,
+                &auction_data,
+                BASE_AUCTION_DATA_SIZE + 2 * mem::size_of::<Bid>(),
+            ),
+            TestAccount::new(bidder, system_program::id(), vec![]),
+            TestAccount::new(spl_token::native_mint::id(), spl_token::id(), vec![]),
+            TestAccount::clock(100),
+            TestAccount::new(spl_token::id(), Pubkey::default(), vec![]),
+            TestAccount::with(
+                pda(
+                    program_id,
+                    &[
+                        PREFIX.as_bytes(),
+                        program_id.as_ref(),
+                        resource.as_ref(),
+                        EXTENDED.as_bytes(),
+                    ],
+                ),
+                *program_id,
+                &extended,
+                MAX_AUCTION_DATA_EXTENDED_SIZE,
+            ),
+            // No commitment or metadata is read on an English auction.
+            TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]),
+            TestAccount::new(Pubkey::new_unique(), *program_id, vec![]),
+            TestAccount::with(
+                pda(
+                    program_id,
+                    &[
+                        PREFIX.as_bytes(),
+                        program_id.as_ref(),
+                        resource.as_ref(),
+                        FEE_SPLITS.as_bytes(),
+                    ],
+                ),
+                *program_id,
+                &fee_splits,
+                AUCTION_FEE_SPLITS_LEN,
+            ),
+            TestAccount::new(recipients[0], system_program::id(), vec![]),
+            TestAccount::new(recipients[1], system_program::id(), vec![]),
+        ]
+    }
+
+    #[test]
+    fn claim_cannot_skip_the_fee_splits() {
+        program_stubs::set_syscall_stubs(Box::new(RentStubs));
+        let program_id = crate::id();
+        let resource = Pubkey::new_unique();
+        let mut accounts = claim_accounts(&program_id, &resource);
+        let mut infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        // The native pot is its own token account.
+        infos.insert(1, infos[1].clone());
+        let claim =
+            |infos: &[AccountInfo]| claim_bid(&program_id, infos, ClaimBidArgs { resource });
+
+        let before: Vec<u64> = infos.iter().map(|info| info.lamports()).collect();
+
+        // Leaving out the extended account, which records the splits, is refused.
+        assert_eq!(claim(&infos[..9]), Err(ProgramError::NotEnoughAccountKeys));
+
+        // So is passing it without the splits, which would pay the destination in full.
+        assert_eq!(
+            claim(&infos[..12]),
+            Err(AuctionError::InvalidFeeSplits.into())
+        );
+
+        claim(&infos).unwrap();
+        assert_eq!(infos[0].lamports(), before[0]);
+        assert_eq!(infos[13].lamports(), before[13] + BID * 7 / 10);
+        assert_eq!(infos[14].lamports(), before[14] + BID * 3 / 10);
+    }
+}
@@ -0,0 +1,252 @@
+//! Claims many winning bids into the destination at once, so an edition with dozens of winners is
+//! settled in a handful of transactions rather than one per winner. Only the fields at the front of
+//! the auction are deserialized, each winner is checked directly against the bids in the account
+//! data, which keeps the cost of each claim flat however many bids the auction holds.
+//!
+//! Only English auctions can be claimed in bulk. Winners of sealed-bid and uniform price auctions
+//! pay a clearing price and are claimed one at a time with ClaimBid. Pots that were already claimed
+//! are skipped, so a batch cut short can simply be sent again.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, AuctionFeeSplits, AuctionState, BidderPot},
+    utils::{
+        assert_authority_or_role, assert_derivation, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, native_pot_withdraw, pot_balance, spl_token_transfer,
+        TokenTransferParams,
+    },
+    EXTENDED, FEE_SPLITS, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+};
+
+/// Accounts given for each winner to claim.
+const ACCOUNTS_PER_WINNER: usize = 3;
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct ClaimBidsArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    destination: &'a AccountInfo<'b>,
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+    fee_splits: &'a AccountInfo<'b>,
+    remaining: &'a [AccountInfo<'b>],
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        destination: next_account_info(account_iter)?,
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+        fee_splits: next_account_info(account_iter)?,
+        remaining: account_iter.as_slice(),
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.mint, accounts.token_program.key)?;
+    assert_signer(accounts.authority)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    Ok(accounts)
+}
+
+pub fn claim_bids(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ClaimBidsArgs,
+) -> ProgramResult {
+    msg!("+ Processing ClaimBids");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    let auction_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        &[auction_bump],
+    ];
+
+    let auction = AuctionData::get_header(accounts.auction)?;
+    if auction.state == AuctionState::Cancelled {
+        return Err(AuctionError::AuctionCancelled.into());
+    }
+    if auction.bid_state_kind != 0 {
+        msg!("Only English auctions can be claimed in bulk");
+        return Err(AuctionError::InvalidState.into());
+    }
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Check authority is correct, or the claimer it delegated to.
+    assert_authority_or_role(
+        &auction.authority,
+        auction_extended.delegate(&auction.authority, auction_extended.claimer),
+        accounts.authority.key,
+    )?;
+
+    // The recipients of the fee splits come first, in the order the splits store them.
+    let fee_splits = if auction_extended.has_fee_splits {
+        assert_derivation(
+            program_id,
+            accounts.fee_splits,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                args.resource.as_ref(),
+                FEE_SPLITS.as_bytes(),
+            ],
+        )?;
+        Some(AuctionFeeSplits::from_account_info(accounts.fee_splits)?)
+    } else {
+        None
+    };
+    let recipient_count = fee_splits
+        .as_ref()
+        .map_or(0, |fee_splits| fee_splits.splits.len());
+    if accounts.remaining.len() < recipient_count {
+        return Err(AuctionError::FeeSplitRecipientMismatch.into());
+    }
+    let (recipients, winners) = accounts.remaining.split_at(recipient_count);
+    if let Some(fee_splits) = &fee_splits {
+        if fee_splits
+            .splits
+            .iter()
+            .zip(recipients.iter())
+            .any(|(split, recipient)| split.recipient != *recipient.key)
+        {
+            return Err(AuctionError::FeeSplitRecipientMismatch.into());
+        }
+    }
+    if winners.is_empty() || winners.len() % ACCOUNTS_PER_WINNER != 0 {
+        return Err(AuctionError::InvalidBidAccount.into());
+    }
+
+    let ended = auction.ended(clock.unix_timestamp)?;
+    let minimum = auction.minimum_price();
+    let native = auction.token_mint == spl_token::native_mint::id();
+
+    for winner in winners.chunks(ACCOUNTS_PER_WINNER) {
+        let (bidder, bidder_pot, bidder_pot_token) = (&winner[0], &winner[1], &winner[2]);
+
+        // User must have won the auction in order to claim their funds.
+        let bid_amount = AuctionData::get_is_winner(accounts.auction, bidder.key)
+            .and_then(|idx| AuctionData::get_winner_bid_amount_at(accounts.auction, idx))
+            .filter(|amount| *amount >= minimum)
+            .ok_or_else(|| {
+                msg!("User {:?} is not winner", bidder.key);
+                ProgramError::from(AuctionError::InvalidState)
+            })?;
+
+        // Auction either must have ended or bidder pay instant_sale_price
+        if !ended {
+            match auction_extended.instant_sale_price {
+                Some(instant_sale_price) if bid_amount >= instant_sale_price => (),
+                _ => return Err(AuctionError::InvalidState.into()),
+            }
+        }
+
+        assert_owned_by(bidder_pot, program_id)?;
+        assert_derivation(
+            program_id,
+            bidder_pot,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                bidder.key.as_ref(),
+            ],
+        )?;
+        let mut pot = BidderPot::from_account_info(bidder_pot)?;
+        if pot.bidder_pot != *bidder_pot_token.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+        if pot.emptied {
+            msg!("Skipping claimed bid of {:?}", bidder.key);
+            continue;
+        }
+
+        let amount = pot_balance(native, accounts.auction, bidder_pot, bidder_pot_token)?;
+        let payouts: Vec<(&AccountInfo, u64)> = match &fee_splits {
+            Some(fee_splits) => recipients.iter().zip(fee_splits.shares(amount)?).collect(),
+            None => vec![(accounts.destination, amount)],
+        };
+
+        for (destination, share) in payouts {
+            if share == 0 {
+                continue;
+            }
+            if native {
+                native_pot_withdraw(bidder_pot, destination, share)?;
+            } else {
+                assert_owned_by(destination, accounts.token_program.key)?;
+                spl_token_transfer(TokenTransferParams {
+                    source: bidder_pot_token.clone(),
+                    destination: destination.clone(),
+                    mint: accounts.mint.clone(),
+                    authority: accounts.auction.clone(),
+                    authority_signer_seeds: auction_seeds,
+                    token_program: accounts.token_program.clone(),
+                    amount: share,
+                })?;
+            }
+        }
+
+        pot.emptied = true;
+        pot.serialize(&mut *bidder_pot.data.borrow_mut())?;
+        auction_extended.pot_drained();
+    }
+
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! Closes an auction that has ended and whose bidder pots have all been settled, returning the rent
+//! held by the auction and auction extended accounts to the account that paid for them. Anyone can
+//! send this, the rent only ever goes back to the payer recorded when the auction was created.
+//! The fee splits of an auction that has them are closed along with it.
+//!
+//! Every bid must have been closed with CloseBidderAccounts first. Bidder accounts can only be
+//! closed through their auction, and left behind they would be picked up again, already settled,
+//! by an auction recreated for the same resource.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, AuctionState},
+    utils::{assert_derivation, assert_owned_by, close_account_raw},
+    EXTENDED, FEE_SPLITS, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CloseAuctionArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    fee_splits: Option<&'a AccountInfo<'b>>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        payer: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        fee_splits: next_account_info(account_iter).ok(),
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    Ok(accounts)
+}
+
+pub fn close_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: CloseAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing CloseAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    if auction.state != AuctionState::Ended && !auction.ended(clock.unix_timestamp)? {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    // Bidders and the authority still need the auction to settle any pot holding funds.
+    if auction_extended.funded_pots > 0 {
+        msg!(
+            "{:?} bidder pots still hold funds",
+            auction_extended.funded_pots
+        );
+        return Err(AuctionError::BidderPotsNotDrained.into());
+    }
+    if auction_extended.open_bidder_pots > 0 {
+        msg!(
+            "{:?} bidder pots are still open",
+            auction_extended.open_bidder_pots
+        );
+        return Err(AuctionError::BidderAccountsNotClosed.into());
+    }
+
+    // Auctions created before the payer was recorded keep their rent.
+    match auction_extended.payer {
+        Some(payer) if payer == *accounts.payer.key => (),
+        _ => return Err(AuctionError::InvalidPayer.into()),
+    }
+
+    close_account_raw(accounts.auction, accounts.payer)?;
+    close_account_raw(accounts.auction_extended, accounts.payer)?;
+
+    if auction_extended.has_fee_splits {
+        let fee_splits = accounts.fee_splits.ok_or(AuctionError::InvalidFeeSplits)?;
+        assert_owned_by(fee_splits, program_id)?;
+        assert_derivation(
+            program_id,
+            fee_splits,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                args.resource.as_ref(),
+                FEE_SPLITS.as_bytes(),
+            ],
+        )?;
+        close_account_raw(fee_splits, accounts.payer)?;
+    }
+
+    Ok(())
+}
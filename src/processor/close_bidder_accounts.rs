@@ -0,0 +1,217 @@
+//! Closes the metadata, pot and pot SPL token account of a bid that has been settled, returning
+//! their rent to the payer recorded in the pot. The bidder can close once they have withdrawn with
+//! CancelBid or RefundExcess, the auction authority once it has claimed a winning bid. Either way
+//! the pot must be empty and neither side can still be owed anything from it.
+//!
+//! The commitment of a sealed bid can be closed alongside the other accounts. The auction counts
+//! the pots still open, it can only be closed itself once this has run for every bid.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, Bid, BidderMetadata, BidderPot},
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_token_program_matches_package,
+        close_account_raw, pot_balance, spl_token_close, TokenCloseParams,
+    },
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CloseBidderAccountsArgs {
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_commitment: Option<&'a AccountInfo<'b>>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    signer: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        signer: next_account_info(account_iter)?,
+        bidder: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        payer: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        bidder_commitment: next_account_info(account_iter).ok(),
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_owned_by(accounts.bidder_meta, program_id)?;
+    assert_signer(accounts.signer)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    if let Some(bidder_commitment) = accounts.bidder_commitment {
+        assert_owned_by(bidder_commitment, program_id)?;
+    }
+
+    Ok(accounts)
+}
+
+pub fn close_bidder_accounts(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CloseBidderAccountsArgs,
+) -> ProgramResult {
+    msg!("+ Processing CloseBidderAccounts");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    // Derive and load Auction.
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    let auction_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        &[auction_bump],
+    ];
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+
+    // Only the wallet that placed the bid and the auction authority may close the bid.
+    if *accounts.signer.key != metadata.bidder_pubkey && *accounts.signer.key != auction.authority {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    // Confirm we're looking at the real SPL account for this bidder.
+    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    let native = auction.is_native();
+    let balance = pot_balance(
+        native,
+        accounts.auction,
+        accounts.bidder_pot,
+        accounts.bidder_pot_token,
+    )?;
+
+    // A winning bid is settled once the authority has claimed it and, where winners pay a clearing
+    // price, the bidder has taken back the rest. Any other bid is settled once withdrawn.
+    let settled = if auction
+        .is_winning_bid(&Bid(*accounts.bidder.key, metadata.last_bid))
+        .is_some()
+    {
+        bidder_pot.emptied && (auction.clearing_price().is_none() || metadata.cancelled)
+    } else {
+        metadata.cancelled
+    };
+
+    if !settled || balance > 0 {
+        return Err(AuctionError::BidderAccountsNotSettled.into());
+    }
+
+    // Pots created before the payer was recorded cannot be closed.
+    match bidder_pot.payer {
+        Some(payer) if payer == *accounts.payer.key => (),
+        _ => return Err(AuctionError::InvalidPayer.into()),
+    }
+
+    // Native SOL pots have no SPL account, closing the pot is enough.
+    if !native {
+        spl_token_close(TokenCloseParams {
+            account: accounts.bidder_pot_token.clone(),
+            destination: accounts.payer.clone(),
+            authority: accounts.auction.clone(),
+            authority_signer_seeds: auction_seeds,
+            token_program: accounts.token_program.clone(),
+        })?;
+    }
+
+    if let Some(bidder_commitment) = accounts.bidder_commitment {
+        assert_derivation(
+            program_id,
+            bidder_commitment,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                accounts.bidder.key.as_ref(),
+                "commitment".as_bytes(),
+            ],
+        )?;
+        close_account_raw(bidder_commitment, accounts.payer)?;
+    }
+
+    close_account_raw(accounts.bidder_meta, accounts.payer)?;
+    close_account_raw(accounts.bidder_pot, accounts.payer)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+    auction_extended.pot_closed();
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,376 @@
+//! Commits a sealed bid to a sealed-bid auction. The bid itself is only known as hash(amount, salt)
+//! until it is revealed, the bidder backs it by moving tokens into their pot. The pot must hold at
+//! least the auction deposit, which is forfeited if the bid is never revealed, and at least the
+//! amount that will be revealed later. Depositing more than the bid hides its size.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, BidState, BidderCommitment, BidderMetadata, BidderPot,
+        Key, BIDDER_COMMITMENT_LEN, BIDDER_METADATA_LEN, BIDDER_POT_LEN,
+    },
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_token_program_matches_package,
+        create_or_allocate_account_raw, spl_token_transfer, unpack_token_account,
+        TokenTransferParams,
+    },
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        hash::Hash,
+        msg,
+        program_error::ProgramError,
+        program_option::COption,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+    std::mem,
+};
+
+/// Arguments for the CommitBid instruction discriminant .
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CommitBidArgs {
+    /// Amount moved into the bidder pot, this must cover the deposit and the bid to be revealed.
+    pub amount: u64,
+    /// hash(amount, salt) of the sealed bid, using big endian bytes for both values.
+    pub commitment: Hash,
+    /// Resource being bid on.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_commitment: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    bidder_token: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+    transfer_authority: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_token: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        bidder_commitment: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        transfer_authority: next_account_info(account_iter)?,
+        payer: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        rent: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_token, accounts.token_program.key)?;
+
+    if !accounts.bidder_pot.data_is_empty() {
+        assert_owned_by(accounts.bidder_pot, program_id)?;
+    }
+    if !accounts.bidder_meta.data_is_empty() {
+        assert_owned_by(accounts.bidder_meta, program_id)?;
+    }
+    if !accounts.bidder_commitment.data_is_empty() {
+        assert_owned_by(accounts.bidder_commitment, program_id)?;
+    }
+
+    assert_owned_by(accounts.mint, accounts.token_program.key)?;
+    assert_owned_by(accounts.bidder_pot_token, accounts.token_program.key)?;
+    assert_signer(accounts.bidder)?;
+    assert_signer(accounts.payer)?;
+    assert_signer(accounts.transfer_authority)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    Ok(accounts)
+}
+
+pub fn commit_bid<'r, 'b: 'r>(
+    program_id: &Pubkey,
+    accounts: &'r [AccountInfo<'b>],
+    args: CommitBidArgs,
+) -> ProgramResult {
+    msg!("+ Processing CommitBid");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    // Derive and load Auction.
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+
+    let deposit = match auction.bid_state {
+        BidState::SealedBid { deposit, .. } => deposit,
+        _ => return Err(AuctionError::NotSealedBidAuction.into()),
+    };
+
+    // The mint provided in this bid must match the one the auction was initialized with.
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Load the clock, commitments are only taken until the auction end time.
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+    if auction.start_if_scheduled(clock.unix_timestamp, auction_extended.start_auction_at)? {
+        auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+    }
+    if !auction.in_commit_phase(clock.unix_timestamp) {
+        return Err(AuctionError::InvalidSealedBidPhase.into());
+    }
+
+    if args.amount < deposit {
+        msg!(
+            "Amount {:?} does not cover the deposit of {:?}",
+            args.amount,
+            deposit
+        );
+        return Err(AuctionError::BidTooSmall.into());
+    }
+
+    // Derive Metadata key and load it.
+    let metadata_bump = assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+
+    // If metadata doesn't exist, create it.
+    if accounts.bidder_meta.owner != program_id {
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.bidder_meta,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            BIDDER_METADATA_LEN,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                accounts.bidder.key.as_ref(),
+                "metadata".as_bytes(),
+                &[metadata_bump],
+            ],
+        )?;
+    } else {
+        // Verify the last commitment was withdrawn before continuing.
+        let bidder_metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+        if bidder_metadata.cancelled == false {
+            return Err(AuctionError::BidAlreadyActive.into());
+        }
+    };
+
+    // Derive the commitment key and create it if this is the first commitment.
+    let commitment_bump = assert_derivation(
+        program_id,
+        accounts.bidder_commitment,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "commitment".as_bytes(),
+        ],
+    )?;
+
+    if accounts.bidder_commitment.owner != program_id {
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.bidder_commitment,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            BIDDER_COMMITMENT_LEN,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                accounts.bidder.key.as_ref(),
+                "commitment".as_bytes(),
+                &[commitment_bump],
+            ],
+        )?;
+    }
+
+    // Derive Pot address, this account wraps/holds an SPL account to transfer tokens into.
+    let pot_bump = assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    // The account within the pot must be owned by us.
+    let actual_account = unpack_token_account(accounts.bidder_pot_token)?;
+    if actual_account.owner != *accounts.auction.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    if actual_account.delegate != COption::None {
+        return Err(AuctionError::DelegateShouldBeNone.into());
+    }
+
+    if actual_account.close_authority != COption::None {
+        return Err(AuctionError::CloseAuthorityShouldBeNone.into());
+    }
+
+    let bump_authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        accounts.auction.key.as_ref(),
+        accounts.bidder.key.as_ref(),
+        &[pot_bump],
+    ];
+
+    // If the bidder pot account is empty, we need to generate one.
+    if accounts.bidder_pot.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.bidder_pot,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            BIDDER_POT_LEN,
+            bump_authority_seeds,
+        )?;
+
+        // Attach SPL token address to pot account.
+        BidderPot {
+            key: Key::BidderPotV1,
+            bidder_pot: *accounts.bidder_pot_token.key,
+            bidder_act: *accounts.bidder.key,
+            auction_act: *accounts.auction.key,
+            emptied: false,
+            payer: Some(*accounts.payer.key),
+        }
+        .serialize(&mut *accounts.bidder_pot.data.borrow_mut())?;
+        auction_extended.pot_opened()?;
+    } else {
+        // Already exists, verify that the pot contains the specified SPL address.
+        let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+        if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+    }
+
+    // Update now we have new bid.
+    auction_extended.total_uncancelled_bids = auction_extended
+        .total_uncancelled_bids
+        .checked_add(1)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+    auction_extended.pot_funded()?;
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    // Confirm payers SPL token balance is enough to back the commitment.
+    let account = unpack_token_account(accounts.bidder_token)?;
+    if account.amount < args.amount {
+        msg!(
+            "Amount is too small: {:?}, compared to account amount of {:?}",
+            args.amount,
+            account.amount
+        );
+        return Err(AuctionError::BalanceTooLow.into());
+    }
+
+    // Transfer amount of SPL token to bid account.
+    let pot_amount = unpack_token_account(accounts.bidder_pot_token)?.amount;
+    spl_token_transfer(TokenTransferParams {
+        source: accounts.bidder_token.clone(),
+        destination: accounts.bidder_pot_token.clone(),
+        mint: accounts.mint.clone(),
+        authority: accounts.transfer_authority.clone(),
+        authority_signer_seeds: bump_authority_seeds,
+        token_program: accounts.token_program.clone(),
+        amount: args.amount,
+    })?;
+
+    // Mints charging a transfer fee deliver less than was sent, what the pot received has to cover
+    // the deposit.
+    let received = unpack_token_account(accounts.bidder_pot_token)?
+        .amount
+        .checked_sub(pot_amount)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+    if received < deposit {
+        msg!(
+            "Received {:?} does not cover the deposit of {:?}",
+            received,
+            deposit
+        );
+        return Err(AuctionError::BidTooSmall.into());
+    }
+
+    BidderCommitment {
+        key: Key::BidderCommitmentV1,
+        bidder_pubkey: *accounts.bidder.key,
+        auction_pubkey: *accounts.auction.key,
+        commitment: args.commitment,
+        revealed: false,
+    }
+    .serialize(&mut *accounts.bidder_commitment.data.borrow_mut())?;
+
+    // The bid amount stays unknown until it is revealed.
+    BidderMetadata {
+        key: Key::BidderMetadataV1,
+        bidder_pubkey: *accounts.bidder.key,
+        auction_pubkey: *accounts.auction.key,
+        last_bid: 0,
+        last_bid_timestamp: clock.unix_timestamp,
+        cancelled: false,
+    }
+    .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -3,11 +3,12 @@ use mem::size_of;
 use crate::{
     errors::AuctionError,
     processor::{
-        AuctionData, AuctionDataExtended, AuctionName, AuctionState, Bid, BidState, PriceFloor,
-        WinnerLimit, BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
+        AntiSnipingPolicy, AuctionData, AuctionDataExtended, AuctionFeeSplits, AuctionName,
+        AuctionState, AuctionType, Bid, BidState, FeeSplit, Key, PriceFloor, WinnerLimit,
+        AUCTION_FEE_SPLITS_LEN, BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
     },
     utils::{assert_derivation, assert_owned_by, create_or_allocate_account_raw},
-    EXTENDED, PREFIX,
+    EXTENDED, FEE_SPLITS, PREFIX,
 };
 
 use {
@@ -52,6 +53,7 @@ struct Accounts<'a, 'b: 'a> {
     payer: &'a AccountInfo<'b>,
     rent: &'a AccountInfo<'b>,
     system: &'a AccountInfo<'b>,
+    fee_splits: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -65,6 +67,7 @@ fn parse_accounts<'a, 'b: 'a>(
         auction_extended: next_account_info(account_iter)?,
         rent: next_account_info(account_iter)?,
         system: next_account_info(account_iter)?,
+        fee_splits: next_account_info(account_iter).ok(),
     };
     Ok(accounts)
 }
@@ -75,6 +78,12 @@ pub fn create_auction(
     args: CreateAuctionArgs,
     instant_sale_price: Option<u64>,
     name: Option<AuctionName>,
+    auction_type: Option<AuctionType>,
+    start_auction_at: Option<UnixTimestamp>,
+    anti_sniping: Option<AntiSnipingPolicy>,
+    fee_splits: Option<Vec<FeeSplit>>,
+    allowlist_root: Option<[u8; 32]>,
+    max_bids_per_bidder: Option<u8>,
 ) -> ProgramResult {
     msg!("+ Processing CreateAuction");
     let accounts = parse_accounts(program_id, accounts)?;
@@ -92,24 +101,104 @@ pub fn create_auction(
         return Err(AuctionError::InvalidAuctionAccount.into());
     }
     // The data must be large enough to hold at least the number of winners.
-    let auction_size = match args.winners {
-        WinnerLimit::Capped(n) => {
-            mem::size_of::<Bid>() * BidState::max_array_size_for(n) + BASE_AUCTION_DATA_SIZE
+    let auction_size = match (&auction_type, &args.winners) {
+        // One slot for the winning bid and one to hold the decay parameters stored after it, which
+        // keeps the size a whole number of bids.
+        (Some(AuctionType::Dutch { .. }), _) => mem::size_of::<Bid>() * 2 + BASE_AUCTION_DATA_SIZE,
+        // Revealed bids plus one slot to hold the reveal window and deposit stored after them.
+        (Some(AuctionType::SealedBid { .. }), WinnerLimit::Capped(n)) => {
+            mem::size_of::<Bid>() * (BidState::max_array_size_for(*n) + 1) + BASE_AUCTION_DATA_SIZE
         }
-        WinnerLimit::Unlimited(_) => BASE_AUCTION_DATA_SIZE,
+        (Some(AuctionType::SealedBid { .. }), WinnerLimit::Unlimited(_)) => {
+            return Err(AuctionError::InvalidSealedBidConfig.into());
+        }
+        // Bids plus one slot to hold the clearing rule stored after them.
+        (Some(AuctionType::UniformPrice(_)), WinnerLimit::Capped(n)) => {
+            mem::size_of::<Bid>() * (BidState::max_array_size_for(*n) + 1) + BASE_AUCTION_DATA_SIZE
+        }
+        (Some(AuctionType::UniformPrice(_)), WinnerLimit::Unlimited(_)) => {
+            return Err(AuctionError::InvalidUniformPriceConfig.into());
+        }
+        (None, WinnerLimit::Capped(n)) => {
+            mem::size_of::<Bid>() * BidState::max_array_size_for(*n) + BASE_AUCTION_DATA_SIZE
+        }
+        (None, WinnerLimit::Unlimited(_)) => BASE_AUCTION_DATA_SIZE,
     };
 
-    let bid_state = match args.winners {
-        WinnerLimit::Capped(n) => BidState::new_english(n),
-        WinnerLimit::Unlimited(_) => BidState::new_open_edition(),
+    let bid_state = match auction_type {
+        Some(AuctionType::Dutch {
+            start_price,
+            floor_price,
+            decay,
+        }) => {
+            if args.winners != WinnerLimit::Capped(1) || start_price < floor_price {
+                return Err(AuctionError::InvalidDutchAuctionConfig.into());
+            }
+            BidState::new_dutch(start_price, floor_price, decay)
+        }
+        Some(AuctionType::SealedBid {
+            reveal_window,
+            deposit,
+        }) => {
+            if reveal_window <= 0 {
+                return Err(AuctionError::InvalidSealedBidConfig.into());
+            }
+            match args.winners {
+                WinnerLimit::Capped(n) => BidState::new_sealed_bid(n, reveal_window, deposit),
+                WinnerLimit::Unlimited(_) => {
+                    return Err(AuctionError::InvalidSealedBidConfig.into())
+                }
+            }
+        }
+        Some(AuctionType::UniformPrice(rule)) => match args.winners {
+            WinnerLimit::Capped(n) => BidState::new_uniform_price(n, rule),
+            WinnerLimit::Unlimited(_) => return Err(AuctionError::InvalidUniformPriceConfig.into()),
+        },
+        None => match args.winners {
+            WinnerLimit::Capped(n) => BidState::new_english(n),
+            WinnerLimit::Unlimited(_) => BidState::new_open_edition(),
+        },
     };
 
-    if let Some(gap_tick) = args.gap_tick_size_percentage {
-        if gap_tick > 100 {
-            return Err(AuctionError::InvalidGapTickSizePercentage.into());
+    bid_state.assert_valid_settings(
+        args.end_auction_at,
+        args.tick_size,
+        args.gap_tick_size_percentage,
+        instant_sale_price,
+        &anti_sniping,
+    )?;
+
+    // Sealed bids are committed without a proof, so allowlists only apply to open bidding.
+    if let (Some(_), BidState::SealedBid { .. }) = (&allowlist_root, &bid_state) {
+        return Err(AuctionError::InvalidSealedBidConfig.into());
+    }
+
+    // Holding several bids only helps a wallet where several bids can win in the open.
+    if let Some(max_bids) = max_bids_per_bidder {
+        let multiple_winners = match (&bid_state, &args.winners) {
+            (BidState::EnglishAuction { .. }, WinnerLimit::Capped(n)) => *n > 1,
+            (BidState::UniformPriceAuction { .. }, WinnerLimit::Capped(n)) => *n > 1,
+            _ => false,
+        };
+        if max_bids == 0 || !multiple_winners {
+            return Err(AuctionError::InvalidBidLimit.into());
         }
     }
 
+    // Sealed-bid and uniform price pots are settled through instructions that only move SPL tokens.
+    if args.token_mint == spl_token::native_mint::id() {
+        match bid_state {
+            BidState::SealedBid { .. } | BidState::UniformPriceAuction { .. } => {
+                return Err(AuctionError::NativeMintNotSupported.into());
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(splits) = &fee_splits {
+        AuctionFeeSplits::validate(splits)?;
+    }
+
     // Create auction account with enough space for a winner tracking.
     create_or_allocate_account_raw(
         *program_id,
@@ -155,16 +244,71 @@ pub fn create_auction(
 
     // Configure extended
     AuctionDataExtended {
+        key: Key::AuctionExtendedV1,
         total_uncancelled_bids: 0,
         tick_size: args.tick_size,
         gap_tick_size_percentage: args.gap_tick_size_percentage,
         instant_sale_price,
         name,
+        start_auction_at,
+        anti_sniping,
+        extensions: 0,
+        total_extension: 0,
+        payer: Some(*accounts.payer.key),
+        funded_pots: 0,
+        has_fee_splits: fee_splits.is_some(),
+        allowlist_root,
+        max_bids_per_bidder,
+        paused_at: None,
+        pending_authority: None,
+        claimer: None,
+        starter: None,
+        delegated_by: None,
+        crank_reward_basis_points: 0,
+        open_bidder_pots: 0,
     }
     .serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
+    // Store the fee splits ClaimBid pays out through.
+    if let Some(splits) = fee_splits {
+        let fee_splits_info = accounts.fee_splits.ok_or(AuctionError::InvalidFeeSplits)?;
+        let fee_splits_bump = assert_derivation(
+            program_id,
+            fee_splits_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                &args.resource.to_bytes(),
+                FEE_SPLITS.as_bytes(),
+            ],
+        )?;
+
+        create_or_allocate_account_raw(
+            *program_id,
+            fee_splits_info,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            AUCTION_FEE_SPLITS_LEN,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                &args.resource.to_bytes(),
+                FEE_SPLITS.as_bytes(),
+                &[fee_splits_bump],
+            ],
+        )?;
+
+        AuctionFeeSplits {
+            key: Key::AuctionFeeSplitsV1,
+            splits,
+        }
+        .serialize(&mut *fee_splits_info.data.borrow_mut())?;
+    }
+
     // Configure Auction.
-    AuctionData {
+    let mut auction = AuctionData {
+        key: Key::AuctionV1,
         authority: args.authority,
         bid_state: bid_state,
         end_auction_at: args.end_auction_at,
@@ -174,8 +318,13 @@ pub fn create_auction(
         price_floor: args.price_floor,
         state: AuctionState::create(),
         token_mint: args.token_mint,
+    };
+
+    // A scheduled auction already knows when it ends, so it ends on time without being started.
+    if let Some(start) = start_auction_at {
+        auction.ended_at = auction.end_time_from(start)?;
     }
-    .serialize(&mut *accounts.auction.data.borrow_mut())?;
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
 
     Ok(())
 }
@@ -4,8 +4,8 @@ use crate::{
     errors::AuctionError,
     processor::create_auction::*,
     processor::{
-        AuctionData, AuctionDataExtended, AuctionName, AuctionState, Bid, BidState, PriceFloor,
-        WinnerLimit, BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
+        AuctionData, AuctionDataExtended, AuctionName, AuctionState, AuctionType, Bid, BidState,
+        PriceFloor, WinnerLimit, BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
     },
     utils::{assert_derivation, assert_owned_by, create_or_allocate_account_raw},
     EXTENDED, PREFIX,
@@ -21,11 +21,14 @@ use {
         program_error::ProgramError,
         pubkey::Pubkey,
     },
-    std::mem,
+    std::{
+        io::{self, Read},
+        mem,
+    },
 };
 
 #[repr(C)]
-#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+#[derive(Clone, BorshSerialize, PartialEq)]
 pub struct CreateAuctionArgsV2 {
     /// How many winners are allowed for this auction. See AuctionData.
     pub winners: WinnerLimit,
@@ -49,6 +52,54 @@ pub struct CreateAuctionArgsV2 {
     pub instant_sale_price: Option<u64>,
     /// Auction name
     pub name: Option<AuctionName>,
+    /// Bidding mechanism, defaults to English or open edition based on the winner limit.
+    pub auction_type: Option<AuctionType>,
+}
+
+// Arguments sent before the auction type was added end right after the name, they read as having
+// none so existing CreateAuctionV2 payloads keep working.
+impl BorshDeserialize for CreateAuctionArgsV2 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let winners = WinnerLimit::deserialize_reader(reader)?;
+        let end_auction_at = Option::<UnixTimestamp>::deserialize_reader(reader)?;
+        let end_auction_gap = Option::<UnixTimestamp>::deserialize_reader(reader)?;
+        let token_mint = Pubkey::deserialize_reader(reader)?;
+        let authority = Pubkey::deserialize_reader(reader)?;
+        let resource = Pubkey::deserialize_reader(reader)?;
+        let price_floor = PriceFloor::deserialize_reader(reader)?;
+        let tick_size = Option::<u64>::deserialize_reader(reader)?;
+        let gap_tick_size_percentage = Option::<u8>::deserialize_reader(reader)?;
+        let instant_sale_price = Option::<u64>::deserialize_reader(reader)?;
+        let name = Option::<AuctionName>::deserialize_reader(reader)?;
+
+        let mut tag = [0u8; 1];
+        let auction_type = match reader.read(&mut tag)? {
+            0 => None,
+            _ if tag[0] == 0 => None,
+            _ if tag[0] == 1 => Some(AuctionType::deserialize_reader(reader)?),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Invalid auction type tag",
+                ))
+            }
+        };
+
+        Ok(CreateAuctionArgsV2 {
+            winners,
+            end_auction_at,
+            end_auction_gap,
+            token_mint,
+            authority,
+            resource,
+            price_floor,
+            tick_size,
+            gap_tick_size_percentage,
+            instant_sale_price,
+            name,
+            auction_type,
+        })
+    }
 }
 
 struct Accounts<'a, 'b: 'a> {
@@ -95,5 +146,55 @@ pub fn create_auction_v2(
         },
         args.instant_sale_price,
         args.name,
+        args.auction_type,
+        None,
+        None,
+        None,
+        None,
+        None,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::PriceDecay;
+
+    fn args(auction_type: Option<AuctionType>) -> CreateAuctionArgsV2 {
+        CreateAuctionArgsV2 {
+            winners: WinnerLimit::Capped(1),
+            end_auction_at: Some(100),
+            end_auction_gap: None,
+            token_mint: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            resource: Pubkey::new_unique(),
+            price_floor: PriceFloor::None([0; 32]),
+            tick_size: None,
+            gap_tick_size_percentage: None,
+            instant_sale_price: None,
+            name: None,
+            auction_type,
+        }
+    }
+
+    #[test]
+    fn auction_type_round_trips() {
+        let dutch = args(Some(AuctionType::Dutch {
+            start_price: 1_000,
+            floor_price: 100,
+            decay: PriceDecay::Linear,
+        }));
+        let decoded = CreateAuctionArgsV2::try_from_slice(&dutch.try_to_vec().unwrap()).unwrap();
+        assert!(decoded == dutch);
+    }
+
+    #[test]
+    fn payload_without_auction_type_reads_as_none() {
+        let legacy = args(None);
+        let mut data = legacy.try_to_vec().unwrap();
+        // Drop the tag of the auction type, as sent before it existed.
+        data.pop();
+        let decoded = CreateAuctionArgsV2::try_from_slice(&data).unwrap();
+        assert!(decoded == legacy);
+    }
+}
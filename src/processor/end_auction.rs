@@ -1,8 +1,13 @@
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionState, Bid, BidState, PriceFloor, WinnerLimit},
-    utils::{assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw},
-    PREFIX,
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, PriceFloor, WinnerLimit,
+    },
+    utils::{
+        assert_authority_or_role, assert_derivation, assert_owned_by, assert_signer,
+        create_or_allocate_account_raw,
+    },
+    EXTENDED, PREFIX,
 };
 
 use {
@@ -37,6 +42,7 @@ struct Accounts<'a, 'b: 'a> {
     authority: &'a AccountInfo<'b>,
     auction: &'a AccountInfo<'b>,
     clock_sysvar: &'a AccountInfo<'b>,
+    auction_extended: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -48,9 +54,13 @@ fn parse_accounts<'a, 'b: 'a>(
         authority: next_account_info(account_iter)?,
         auction: next_account_info(account_iter)?,
         clock_sysvar: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter).ok(),
     };
     assert_owned_by(accounts.auction, program_id)?;
     assert_signer(accounts.authority)?;
+    if let Some(auction_extended) = accounts.auction_extended {
+        assert_owned_by(auction_extended, program_id)?;
+    }
     Ok(accounts)
 }
 
@@ -95,10 +105,26 @@ pub fn end_auction<'a, 'b: 'a>(
     // End auction.
     let mut auction = AuctionData::from_account_info(accounts.auction)?;
 
-    // Check authority is correct.
-    if auction.authority != *accounts.authority.key {
-        return Err(AuctionError::InvalidAuthority.into());
-    }
+    // Check authority is correct, or the starter it delegated to, who is only known when the
+    // extended account is passed.
+    let starter = match accounts.auction_extended {
+        Some(auction_extended) => {
+            assert_derivation(
+                program_id,
+                auction_extended,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    args.resource.as_ref(),
+                    EXTENDED.as_bytes(),
+                ],
+            )?;
+            let auction_extended = AuctionDataExtended::from_account_info(auction_extended)?;
+            auction_extended.delegate(&auction.authority, auction_extended.starter)
+        }
+        None => None,
+    };
+    assert_authority_or_role(&auction.authority, starter, accounts.authority.key)?;
 
     // As long as it hasn't already ended.
     if auction.ended_at.is_some() {
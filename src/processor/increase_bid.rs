@@ -0,0 +1,275 @@
+//! Raises a bid on a running auction without cancelling it first. Only the difference between the
+//! new total and the current bid is paid into the pot, and the bid moves up among the others in
+//! place, so nobody can slip in between a cancel and a new bid.
+//!
+//! Only English and uniform price auctions take increases, bids on the others are final once
+//! placed. Bids a wallet holds beyond its first are increased by passing their bid key as the
+//! bidder and the wallet as the bid owner.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, AuctionState, Bid, BidderMetadata, BidderPot},
+    utils::{
+        assert_bid_owner, assert_derivation, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, native_pot_deposit, spl_token_transfer,
+        unpack_token_account, TokenTransferParams,
+    },
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct IncreaseBidArgs {
+    /// New total of the bid, only the difference from the current bid is paid.
+    pub amount: u64,
+    /// Resource being bid on.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    bidder_token: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+    transfer_authority: &'a AccountInfo<'b>,
+    bid_owner: Option<&'a AccountInfo<'b>>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_token: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        transfer_authority: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+        bid_owner: next_account_info(account_iter).ok(),
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_owned_by(accounts.bidder_meta, program_id)?;
+    assert_owned_by(accounts.mint, accounts.token_program.key)?;
+    assert_signer(accounts.transfer_authority)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    Ok(accounts)
+}
+
+pub fn increase_bid(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: IncreaseBidArgs,
+) -> ProgramResult {
+    msg!("+ Processing IncreaseBid");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+
+    // The mint provided in this bid must match the one the auction was initialized with.
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+    let native = auction.is_native();
+
+    // Can't bid on an auction that isn't running.
+    if auction.state == AuctionState::Paused {
+        return Err(AuctionError::AuctionPaused.into());
+    }
+    if auction.state != AuctionState::Started || auction.ended(clock.unix_timestamp)? {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Only a live bid can be increased, and only by the wallet that placed it.
+    assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+    assert_bid_owner(accounts.bidder, accounts.bid_owner, &metadata.bidder_pubkey)?;
+    if metadata.cancelled {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    // Derive Pot address, also used as the authoriser of the SPL pot.
+    let pot_bump = assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    let bump_authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        accounts.auction.key.as_ref(),
+        accounts.bidder.key.as_ref(),
+        &[pot_bump],
+    ];
+
+    // Confirm we're looking at the real pot account for this bidder.
+    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    let mut new_total = args.amount;
+    if let Some(instant_sale_price) = auction_extended.instant_sale_price {
+        if new_total > instant_sale_price {
+            msg!(
+                "Received amount is more than instant_sale_price so it was reduced to {:?}",
+                instant_sale_price
+            );
+            new_total = instant_sale_price;
+        }
+    }
+
+    if new_total <= metadata.last_bid {
+        msg!(
+            "New total {:?} does not increase the bid of {:?}",
+            new_total,
+            metadata.last_bid
+        );
+        return Err(AuctionError::BidTooSmall.into());
+    }
+    let delta = new_total - metadata.last_bid;
+
+    // Pay only the difference into the pot.
+    let received = if native {
+        assert_signer(accounts.bidder_token)?;
+        if accounts.bidder_token.lamports() < delta {
+            return Err(AuctionError::BalanceTooLow.into());
+        }
+        native_pot_deposit(
+            accounts.bidder_token,
+            accounts.bidder_pot,
+            accounts.system,
+            delta,
+        )?;
+        delta
+    } else {
+        assert_owned_by(accounts.bidder_token, accounts.token_program.key)?;
+        if unpack_token_account(accounts.bidder_token)?.amount < delta {
+            return Err(AuctionError::BalanceTooLow.into());
+        }
+
+        let pot_amount = unpack_token_account(accounts.bidder_pot_token)?.amount;
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_token.clone(),
+            destination: accounts.bidder_pot_token.clone(),
+            mint: accounts.mint.clone(),
+            authority: accounts.transfer_authority.clone(),
+            authority_signer_seeds: bump_authority_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: delta,
+        })?;
+
+        // Mints charging a transfer fee deliver less than was sent. The bid only grows by what
+        // the pot received, which is what the tick size, gap and instant sale price are checked
+        // against.
+        let received = unpack_token_account(accounts.bidder_pot_token)?
+            .amount
+            .checked_sub(pot_amount)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        if received != delta {
+            msg!("Pot received {:?} of the {:?} raise", received, delta);
+        }
+        received
+    };
+    if received == 0 {
+        return Err(AuctionError::BidTooSmall.into());
+    }
+    let new_bid = metadata
+        .last_bid
+        .checked_add(received)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+
+    // Move the bid to its new place, checked against the tick size and gap like a fresh bid.
+    auction.last_bid = Some(clock.unix_timestamp);
+    let bid_capacity = auction.bid_capacity(accounts.auction);
+    auction.increase_bid(
+        metadata.last_bid,
+        Bid(*accounts.bidder.key, new_bid),
+        auction_extended.tick_size,
+        auction_extended.gap_tick_size_percentage,
+        clock.unix_timestamp,
+        auction_extended.instant_sale_price,
+        bid_capacity,
+    )?;
+    if auction.extend_for_late_bid(clock.unix_timestamp, &mut auction_extended)? {
+        auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+    }
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    BidderMetadata {
+        last_bid: new_bid,
+        last_bid_timestamp: clock.unix_timestamp,
+        ..metadata
+    }
+    .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
+
+    Ok(())
+}
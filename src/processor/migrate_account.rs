@@ -0,0 +1,255 @@
+//! Upgrades an account created before accounts carried a leading Key byte to the current layout.
+//! The data is moved up by one byte behind the key, and the account grows to make room, the payer
+//! covering any extra rent. Bidder pots created before the payer was recorded are migrated with no
+//! payer. Anyone can send this, the account is derived from the given seeds so the key written
+//! always matches what the account holds.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, Bid, BidderCommitment, BidderMetadata, BidderPot, Key,
+        BASE_AUCTION_DATA_SIZE, BIDDER_COMMITMENT_LEN, BIDDER_METADATA_LEN, BIDDER_POT_LEN,
+        MAX_AUCTION_DATA_EXTENDED_SIZE,
+    },
+    utils::{assert_derivation, assert_owned_by, assert_signer, resize_account_raw},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    std::mem,
+};
+
+// Sizes of the headerless layouts.
+const LEGACY_BASE_AUCTION_DATA_SIZE: usize = BASE_AUCTION_DATA_SIZE - 1;
+// The deployed extended layout, its fields followed by padding up to 219 bytes.
+const LEGACY_AUCTION_DATA_EXTENDED_SIZE: usize = 8 + 9 + 2 + 9 + 33 + 158;
+const LEGACY_BIDDER_METADATA_LEN: usize = BIDDER_METADATA_LEN - 1;
+const LEGACY_BIDDER_COMMITMENT_LEN: usize = BIDDER_COMMITMENT_LEN - 1;
+const LEGACY_BIDDER_POT_LEN: usize = BIDDER_POT_LEN - 1;
+// Bidder pots created before the payer was recorded.
+const LEGACY_BIDDER_POT_NO_PAYER_LEN: usize = BIDDER_POT_LEN - 1 - 33;
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct MigrateAccountArgs {
+    /// Type of the account being migrated.
+    pub key: Key,
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+    /// Bidder the account belongs to, required for bidder metadata, pots and commitments.
+    pub bidder: Option<Pubkey>,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    account: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        account: next_account_info(account_iter)?,
+        payer: next_account_info(account_iter)?,
+        rent: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.account, program_id)?;
+    assert_signer(accounts.payer)?;
+    Ok(accounts)
+}
+
+pub fn migrate_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MigrateAccountArgs,
+) -> ProgramResult {
+    msg!("+ Processing MigrateAccount");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    let (auction_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+        program_id,
+    );
+
+    // Confirm the account is the one the key names, so an account of one type can never be given
+    // the key of another.
+    match args.key {
+        Key::AuctionV1 => {
+            assert_derivation(
+                program_id,
+                accounts.account,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    args.resource.as_ref(),
+                ],
+            )?;
+        }
+        Key::AuctionExtendedV1 => {
+            assert_derivation(
+                program_id,
+                accounts.account,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    args.resource.as_ref(),
+                    EXTENDED.as_bytes(),
+                ],
+            )?;
+        }
+        Key::BidderMetadataV1 | Key::BidderPotV1 | Key::BidderCommitmentV1 => {
+            let bidder = args.bidder.ok_or(AuctionError::InvalidMigration)?;
+            let suffix: &[u8] = match args.key {
+                Key::BidderMetadataV1 => "metadata".as_bytes(),
+                Key::BidderCommitmentV1 => "commitment".as_bytes(),
+                _ => &[],
+            };
+            let mut seeds = vec![
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                auction_key.as_ref(),
+                bidder.as_ref(),
+            ];
+            if !suffix.is_empty() {
+                seeds.push(suffix);
+            }
+            assert_derivation(program_id, accounts.account, &seeds)?;
+        }
+        // Fee splits were introduced with the key byte and never need migrating.
+        Key::Uninitialized | Key::AuctionFeeSplitsV1 => {
+            return Err(AuctionError::InvalidMigration.into())
+        }
+    }
+
+    let migrated = match args.key {
+        Key::AuctionV1 => AuctionData::from_account_info(accounts.account).is_ok(),
+        Key::AuctionExtendedV1 => AuctionDataExtended::from_account_info(accounts.account).is_ok(),
+        Key::BidderMetadataV1 => BidderMetadata::from_account_info(accounts.account).is_ok(),
+        Key::BidderPotV1 => BidderPot::from_account_info(accounts.account).is_ok(),
+        Key::BidderCommitmentV1 => BidderCommitment::from_account_info(accounts.account).is_ok(),
+        Key::Uninitialized | Key::AuctionFeeSplitsV1 => false,
+    };
+    if migrated {
+        return Err(AuctionError::AccountAlreadyMigrated.into());
+    }
+
+    let len = accounts.account.data_len();
+    let new_len = migrated_len(&args.key, len)?;
+
+    msg!("Migrating account from {} to {} bytes", len, new_len);
+    let legacy_data = accounts.account.data.borrow().to_vec();
+    resize_account_raw(
+        accounts.account,
+        accounts.rent,
+        accounts.system,
+        accounts.payer,
+        new_len,
+    )?;
+
+    // The resized tail is zeroed, which older pots read as having no payer.
+    let mut data = accounts.account.data.borrow_mut();
+    data[0] = args.key as u8;
+    data[1..=len].copy_from_slice(&legacy_data);
+
+    Ok(())
+}
+
+/// Size of a legacy account once migrated, given the size it was created with. Accounts of any
+/// other size were never deployed and are refused.
+fn migrated_len(key: &Key, len: usize) -> Result<usize, ProgramError> {
+    match key {
+        Key::AuctionV1
+            if len >= LEGACY_BASE_AUCTION_DATA_SIZE
+                && (len - LEGACY_BASE_AUCTION_DATA_SIZE) % mem::size_of::<Bid>() == 0 =>
+        {
+            Ok(len + 1)
+        }
+        Key::AuctionExtendedV1 if len == LEGACY_AUCTION_DATA_EXTENDED_SIZE => {
+            Ok(MAX_AUCTION_DATA_EXTENDED_SIZE)
+        }
+        Key::BidderMetadataV1 if len == LEGACY_BIDDER_METADATA_LEN => Ok(BIDDER_METADATA_LEN),
+        Key::BidderPotV1
+            if len == LEGACY_BIDDER_POT_LEN || len == LEGACY_BIDDER_POT_NO_PAYER_LEN =>
+        {
+            Ok(BIDDER_POT_LEN)
+        }
+        Key::BidderCommitmentV1 if len == LEGACY_BIDDER_COMMITMENT_LEN => Ok(BIDDER_COMMITMENT_LEN),
+        _ => Err(AuctionError::InvalidMigration.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_deployed_legacy_sizes_migrate() {
+        assert_eq!(migrated_len(&Key::AuctionExtendedV1, 219), Ok(352));
+        assert_eq!(MAX_AUCTION_DATA_EXTENDED_SIZE, 352);
+        assert_eq!(
+            migrated_len(&Key::AuctionExtendedV1, 218),
+            Err(AuctionError::InvalidMigration.into())
+        );
+        assert_eq!(
+            migrated_len(&Key::AuctionExtendedV1, 352),
+            Err(AuctionError::InvalidMigration.into())
+        );
+
+        // Auctions hold any number of whole bids after the header.
+        let bid = mem::size_of::<Bid>();
+        for len in &[
+            LEGACY_BASE_AUCTION_DATA_SIZE,
+            LEGACY_BASE_AUCTION_DATA_SIZE + 3 * bid,
+        ] {
+            assert_eq!(migrated_len(&Key::AuctionV1, *len), Ok(len + 1));
+        }
+        for len in &[
+            LEGACY_BASE_AUCTION_DATA_SIZE - 1,
+            LEGACY_BASE_AUCTION_DATA_SIZE + bid + 1,
+        ] {
+            assert_eq!(
+                migrated_len(&Key::AuctionV1, *len),
+                Err(AuctionError::InvalidMigration.into())
+            );
+        }
+
+        // Pots are migrated whether or not they recorded a payer.
+        for len in &[BIDDER_POT_LEN - 1, BIDDER_POT_LEN - 34] {
+            assert_eq!(migrated_len(&Key::BidderPotV1, *len), Ok(BIDDER_POT_LEN));
+        }
+        assert_eq!(
+            migrated_len(&Key::BidderPotV1, BIDDER_POT_LEN),
+            Err(AuctionError::InvalidMigration.into())
+        );
+
+        assert_eq!(
+            migrated_len(&Key::BidderMetadataV1, BIDDER_METADATA_LEN - 1),
+            Ok(BIDDER_METADATA_LEN)
+        );
+        assert_eq!(
+            migrated_len(&Key::BidderCommitmentV1, BIDDER_COMMITMENT_LEN - 1),
+            Ok(BIDDER_COMMITMENT_LEN)
+        );
+        assert_eq!(
+            migrated_len(&Key::AuctionFeeSplitsV1, 0),
+            Err(AuctionError::InvalidMigration.into())
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Freezes a running auction while the authority looks into a problem with it. No bids are taken
+//! while it is paused, though bidders can still cancel, and unlike EndAuction it can be resumed
+//! with ResumeAuction.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended},
+    utils::{assert_derivation, assert_owned_by, assert_signer},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct PauseAuctionArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.authority)?;
+    Ok(accounts)
+}
+
+pub fn pause_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: PauseAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing PauseAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Check authority is correct.
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    // A scheduled auction past its start time is running even if nobody has bid yet.
+    auction.start_if_scheduled(clock.unix_timestamp, auction_extended.start_auction_at)?;
+    auction.pause(clock.unix_timestamp, &mut auction_extended)?;
+
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
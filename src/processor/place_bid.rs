@@ -9,23 +9,31 @@
 //!
 //! A few solutions come to mind: don't allow cancelling bids, and simply prune all bids that
 //! are not winning bids from the state.
+//!
+//! Auctions with an allowlist only take bids from bidders that prove their place in its Merkle
+//! tree, checked before any of their accounts are created.
+//!
+//! Where an auction lets a wallet hold several bids, each one after the first is placed under its
+//! own bid key, see bid_key, which its pot and metadata are derived from in place of the wallet.
 
 use borsh::try_to_vec_with_schema;
 
 use crate::{
     errors::AuctionError,
+    merkle,
     processor::{
-        AuctionData, AuctionDataExtended, AuctionState, Bid, BidderMetadata, BidderPot, PriceFloor,
+        bid_key, AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, BidderMetadata,
+        BidderPot, Key, PriceFloor,
     },
     utils::{
-        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
-        assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
-        TokenTransferParams,
+        assert_derivation, assert_owned_by, assert_signer, assert_token_program_matches_package,
+        create_or_allocate_account_raw, native_pot_deposit, spl_token_transfer,
+        unpack_token_account, TokenTransferParams,
     },
     EXTENDED, PREFIX,
 };
 
-use super::BIDDER_METADATA_LEN;
+use super::{BIDDER_METADATA_LEN, BIDDER_POT_LEN};
 
 use {
     borsh::{BorshDeserialize, BorshSerialize},
@@ -43,7 +51,6 @@ use {
         system_instruction::create_account,
         sysvar::{clock::Clock, Sysvar},
     },
-    spl_token::state::Account,
     std::mem,
 };
 
@@ -98,7 +105,6 @@ fn parse_accounts<'a, 'b: 'a>(
 
     assert_owned_by(accounts.auction, program_id)?;
     assert_owned_by(accounts.auction_extended, program_id)?;
-    assert_owned_by(accounts.bidder_token, &spl_token::id())?;
 
     if !accounts.bidder_pot.data_is_empty() {
         assert_owned_by(accounts.bidder_pot, program_id)?;
@@ -107,17 +113,12 @@ fn parse_accounts<'a, 'b: 'a>(
         assert_owned_by(accounts.bidder_meta, program_id)?;
     }
 
-    assert_owned_by(accounts.mint, &spl_token::id())?;
-    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+    assert_owned_by(accounts.mint, accounts.token_program.key)?;
     assert_signer(accounts.bidder)?;
     assert_signer(accounts.payer)?;
     assert_signer(accounts.transfer_authority)?;
     assert_token_program_matches_package(accounts.token_program)?;
 
-    if *accounts.token_program.key != spl_token::id() {
-        return Err(AuctionError::InvalidTokenProgram.into());
-    }
-
     Ok(accounts)
 }
 
@@ -126,6 +127,8 @@ pub fn place_bid<'r, 'b: 'r>(
     program_id: &Pubkey,
     accounts: &'r [AccountInfo<'b>],
     args: PlaceBidArgs,
+    proof: Option<Vec<[u8; 32]>>,
+    bid_index: u8,
 ) -> ProgramResult {
     msg!("+ Processing PlaceBid");
     let accounts = parse_accounts(program_id, accounts)?;
@@ -133,6 +136,22 @@ pub fn place_bid<'r, 'b: 'r>(
     // Load the auction and verify this bid is valid.
     let mut auction = AuctionData::from_account_info(accounts.auction)?;
 
+    // No bids are taken while the authority has the auction paused.
+    if auction.state == AuctionState::Paused {
+        return Err(AuctionError::AuctionPaused.into());
+    }
+
+    // Sealed-bid auctions take their bids through CommitBid and RevealBid instead.
+    if let BidState::SealedBid { .. } = auction.bid_state {
+        return Err(AuctionError::BidMustBeSealed.into());
+    }
+
+    // The mint provided in this bid must match the one the auction was initialized with.
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+    let native = auction.is_native();
+
     // Load the clock, used for various auction timing.
     let clock = Clock::from_account_info(accounts.clock_sysvar)?;
 
@@ -143,6 +162,39 @@ pub fn place_bid<'r, 'b: 'r>(
         msg!("Auction ended!");
         return Ok(());
     }
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let mut auction_extended: AuctionDataExtended =
+        AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Only bidders on the allowlist may bid, checked before paying for any of their accounts.
+    if let Some(root) = &auction_extended.allowlist_root {
+        let proof = proof.ok_or(AuctionError::BidderNotAllowed)?;
+        if !merkle::verify(root, accounts.bidder.key, &proof) {
+            return Err(AuctionError::BidderNotAllowed.into());
+        }
+    }
+
+    // Wallets can hold as many bids at once as the auction allows, each under its own key.
+    if bid_index >= auction_extended.max_bids_per_bidder.unwrap_or(1) {
+        return Err(AuctionError::BidLimitReached.into());
+    }
+    let bid_key = bid_key(
+        program_id,
+        accounts.auction.key,
+        accounts.bidder.key,
+        bid_index,
+    );
+
     // Derive Metadata key and load it.
     let metadata_bump = assert_derivation(
         program_id,
@@ -151,7 +203,7 @@ pub fn place_bid<'r, 'b: 'r>(
             PREFIX.as_bytes(),
             program_id.as_ref(),
             accounts.auction.key.as_ref(),
-            accounts.bidder.key.as_ref(),
+            bid_key.as_ref(),
             "metadata".as_bytes(),
         ],
     )?;
@@ -171,7 +223,7 @@ pub fn place_bid<'r, 'b: 'r>(
                 PREFIX.as_bytes(),
                 program_id.as_ref(),
                 accounts.auction.key.as_ref(),
-                accounts.bidder.key.as_ref(),
+                bid_key.as_ref(),
                 "metadata".as_bytes(),
                 &[metadata_bump],
             ],
@@ -194,22 +246,34 @@ pub fn place_bid<'r, 'b: 'r>(
             PREFIX.as_bytes(),
             program_id.as_ref(),
             accounts.auction.key.as_ref(),
-            accounts.bidder.key.as_ref(),
+            bid_key.as_ref(),
         ],
     )?;
 
-    // The account within the pot must be owned by us.
-    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
-    if actual_account.owner != *accounts.auction.key {
-        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
-    }
+    if native {
+        // Native SOL bids are held by the pot itself, paid from the wallet given as the token
+        // account.
+        if accounts.bidder_pot_token.key != accounts.bidder_pot.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+        assert_signer(accounts.bidder_token)?;
+    } else {
+        assert_owned_by(accounts.bidder_token, accounts.token_program.key)?;
+        assert_owned_by(accounts.bidder_pot_token, accounts.token_program.key)?;
 
-    if actual_account.delegate != COption::None {
-        return Err(AuctionError::DelegateShouldBeNone.into());
-    }
+        // The account within the pot must be owned by us.
+        let actual_account = unpack_token_account(accounts.bidder_pot_token)?;
+        if actual_account.owner != *accounts.auction.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+
+        if actual_account.delegate != COption::None {
+            return Err(AuctionError::DelegateShouldBeNone.into());
+        }
 
-    if actual_account.close_authority != COption::None {
-        return Err(AuctionError::CloseAuthorityShouldBeNone.into());
+        if actual_account.close_authority != COption::None {
+            return Err(AuctionError::CloseAuthorityShouldBeNone.into());
+        }
     }
 
     // Derive and load Auction.
@@ -223,6 +287,10 @@ pub fn place_bid<'r, 'b: 'r>(
         ],
     )?;
 
+    // A scheduled auction counts as started once its start time has passed, the new state is
+    // saved along with the bid.
+    auction.start_if_scheduled(clock.unix_timestamp, auction_extended.start_auction_at)?;
+
     // Can't bid on an auction that isn't running.
     if auction.state != AuctionState::Started {
         return Err(AuctionError::InvalidState.into());
@@ -232,7 +300,7 @@ pub fn place_bid<'r, 'b: 'r>(
         PREFIX.as_bytes(),
         program_id.as_ref(),
         accounts.auction.key.as_ref(),
-        accounts.bidder.key.as_ref(),
+        bid_key.as_ref(),
         &[pot_bump],
     ];
 
@@ -244,16 +312,21 @@ pub fn place_bid<'r, 'b: 'r>(
             accounts.rent,
             accounts.system,
             accounts.payer,
-            mem::size_of::<BidderPot>(),
+            BIDDER_POT_LEN,
             bump_authority_seeds,
         )?;
 
         // Attach SPL token address to pot account.
-        let mut pot = BidderPot::from_account_info(accounts.bidder_pot)?;
-        pot.bidder_pot = *accounts.bidder_pot_token.key;
-        pot.bidder_act = *accounts.bidder.key;
-        pot.auction_act = *accounts.auction.key;
-        pot.serialize(&mut *accounts.bidder_pot.data.borrow_mut())?;
+        BidderPot {
+            key: Key::BidderPotV1,
+            bidder_pot: *accounts.bidder_pot_token.key,
+            bidder_act: bid_key,
+            auction_act: *accounts.auction.key,
+            emptied: false,
+            payer: Some(*accounts.payer.key),
+        }
+        .serialize(&mut *accounts.bidder_pot.data.borrow_mut())?;
+        auction_extended.pot_opened()?;
     } else {
         // Already exists, verify that the pot contains the specified SPL address.
         let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
@@ -263,22 +336,11 @@ pub fn place_bid<'r, 'b: 'r>(
     }
 
     // Update now we have new bid.
-    assert_derivation(
-        program_id,
-        accounts.auction_extended,
-        &[
-            PREFIX.as_bytes(),
-            program_id.as_ref(),
-            args.resource.as_ref(),
-            EXTENDED.as_bytes(),
-        ],
-    )?;
-    let mut auction_extended: AuctionDataExtended =
-        AuctionDataExtended::from_account_info(accounts.auction_extended)?;
     auction_extended.total_uncancelled_bids = auction_extended
         .total_uncancelled_bids
         .checked_add(1)
         .ok_or(AuctionError::NumericalOverflowError)?;
+    auction_extended.pot_funded()?;
     auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
     let mut bid_price = args.amount;
@@ -290,43 +352,95 @@ pub fn place_bid<'r, 'b: 'r>(
         }
     }
 
-    // Confirm payers SPL token balance is enough to pay the bid.
-    let account: Account = Account::unpack_from_slice(&accounts.bidder_token.data.borrow())?;
-    if account.amount.saturating_sub(bid_price) < 0 {
-        msg!(
-            "Amount is too small: {:?}, compared to account amount of {:?}",
-            bid_price,
-            account.amount
-        );
-        return Err(AuctionError::BalanceTooLow.into());
+    // A Dutch auction sells at the current asking price, anything above it is not taken.
+    if let Some(price) = auction.dutch_price(clock.unix_timestamp)? {
+        if bid_price > price {
+            msg!(
+                "Received amount is more than the current price so it was reduced to {:?}",
+                price
+            );
+            bid_price = price;
+        }
     }
 
-    // Transfer amount of SPL token to bid account.
-    spl_token_transfer(TokenTransferParams {
-        source: accounts.bidder_token.clone(),
-        destination: accounts.bidder_pot_token.clone(),
-        authority: accounts.transfer_authority.clone(),
-        authority_signer_seeds: bump_authority_seeds,
-        token_program: accounts.token_program.clone(),
-        amount: bid_price,
-    })?;
+    let received = if native {
+        // Confirm the payers wallet holds enough lamports to pay the bid.
+        if accounts.bidder_token.lamports() < bid_price {
+            msg!(
+                "Amount is too small: {:?}, compared to account lamports of {:?}",
+                bid_price,
+                accounts.bidder_token.lamports()
+            );
+            return Err(AuctionError::BalanceTooLow.into());
+        }
+
+        // Transfer lamports to bid account.
+        native_pot_deposit(
+            accounts.bidder_token,
+            accounts.bidder_pot,
+            accounts.system,
+            bid_price,
+        )?;
+        bid_price
+    } else {
+        // Confirm payers SPL token balance is enough to pay the bid.
+        let account = unpack_token_account(accounts.bidder_token)?;
+        if account.amount.saturating_sub(bid_price) < 0 {
+            msg!(
+                "Amount is too small: {:?}, compared to account amount of {:?}",
+                bid_price,
+                account.amount
+            );
+            return Err(AuctionError::BalanceTooLow.into());
+        }
+
+        // Transfer amount of SPL token to bid account.
+        let pot_amount = unpack_token_account(accounts.bidder_pot_token)?.amount;
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_token.clone(),
+            destination: accounts.bidder_pot_token.clone(),
+            mint: accounts.mint.clone(),
+            authority: accounts.transfer_authority.clone(),
+            authority_signer_seeds: bump_authority_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: bid_price,
+        })?;
+
+        // Mints charging a transfer fee deliver less than was sent. The bid is what the pot
+        // received, so the tick size, current price and instant sale price are checked against
+        // the amount that can actually be claimed.
+        let received = unpack_token_account(accounts.bidder_pot_token)?
+            .amount
+            .checked_sub(pot_amount)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        if received != bid_price {
+            msg!("Pot received {:?} of the {:?} sent", received, bid_price);
+        }
+        received
+    };
 
     // Serialize new Auction State
     auction.last_bid = Some(clock.unix_timestamp);
+    let bid_capacity = auction.bid_capacity(accounts.auction);
     auction.place_bid(
-        Bid(*accounts.bidder.key, bid_price),
+        Bid(bid_key, received),
         auction_extended.tick_size,
         auction_extended.gap_tick_size_percentage,
         clock.unix_timestamp,
         auction_extended.instant_sale_price,
+        bid_capacity,
     )?;
+    if auction.extend_for_late_bid(clock.unix_timestamp, &mut auction_extended)? {
+        auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+    }
     auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
 
     // Update latest metadata with results from the bid.
     BidderMetadata {
+        key: Key::BidderMetadataV1,
         bidder_pubkey: *accounts.bidder.key,
         auction_pubkey: *accounts.auction.key,
-        last_bid: bid_price,
+        last_bid: received,
         last_bid_timestamp: clock.unix_timestamp,
         cancelled: false,
     }
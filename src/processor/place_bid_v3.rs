@@ -0,0 +1,37 @@
+use crate::processor::place_bid::*;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey},
+};
+
+/// Arguments for the PlaceBidV3 instruction discriminant.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct PlaceBidArgsV3 {
+    /// Size of the bid being placed. The user must have enough SOL to satisfy this amount.
+    pub amount: u64,
+    /// Resource being bid on.
+    pub resource: Pubkey,
+    /// Merkle proof of the bidder on the auction allowlist, empty when the auction has none.
+    pub proof: Vec<[u8; 32]>,
+    /// Which of the bidders bids this is, from zero up to the auctions limit per bidder.
+    pub bid_index: u8,
+}
+
+pub fn place_bid_v3<'r, 'b: 'r>(
+    program_id: &Pubkey,
+    accounts: &'r [AccountInfo<'b>],
+    args: PlaceBidArgsV3,
+) -> ProgramResult {
+    place_bid(
+        program_id,
+        accounts,
+        PlaceBidArgs {
+            amount: args.amount,
+            resource: args.resource,
+        },
+        Some(args.proof),
+        args.bid_index,
+    )
+}
@@ -0,0 +1,246 @@
+//! Refunds losing bids of an auction that is over, so funds are not left stranded in the pots of
+//! bidders that never come back for them. Anyone can send this, each bid is only ever refunded to
+//! an account of the wallet that placed it, and winners are skipped using the same checks as
+//! CancelBid. Whoever sends it can be paid a small share of each refund, set by the authority with
+//! SetCrankReward.
+//!
+//! Sealed-bid auctions are left to CancelBid unless cancelled, as unrevealed bids owe a deposit
+//! that only the commitment account records. Open editions are likewise left alone unless
+//! cancelled, none of their bids ranks as a winner so every one would pass for a losing bid.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, BidderMetadata, BidderPot,
+    },
+    utils::{
+        assert_derivation, assert_owned_by, assert_signer, assert_token_program_matches_package,
+        native_pot_withdraw, pot_balance, spl_token_transfer, unpack_token_account,
+        TokenTransferParams,
+    },
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+};
+
+/// Accounts given for each bid to refund.
+const ACCOUNTS_PER_BID: usize = 5;
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct RefundBidsArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    cranker: &'a AccountInfo<'b>,
+    cranker_token: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+    bids: &'a [AccountInfo<'b>],
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        cranker: next_account_info(account_iter)?,
+        cranker_token: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+        bids: account_iter.as_slice(),
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.mint, accounts.token_program.key)?;
+    assert_signer(accounts.cranker)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    if accounts.bids.is_empty() || accounts.bids.len() % ACCOUNTS_PER_BID != 0 {
+        return Err(AuctionError::InvalidBidAccount.into());
+    }
+
+    Ok(accounts)
+}
+
+pub fn refund_bids(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RefundBidsArgs,
+) -> ProgramResult {
+    msg!("+ Processing RefundBids");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    let auction_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        &[auction_bump],
+    ];
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Losing bids are only known once the auction is over.
+    if !auction.ended(clock.unix_timestamp)? {
+        return Err(AuctionError::InvalidState.into());
+    }
+    match auction.bid_state {
+        BidState::SealedBid { .. } | BidState::OpenEdition { .. }
+            if auction.state != AuctionState::Cancelled =>
+        {
+            return Err(AuctionError::InvalidState.into())
+        }
+        _ => (),
+    }
+
+    let native = auction.is_native();
+    if !native {
+        assert_owned_by(accounts.cranker_token, accounts.token_program.key)?;
+    }
+
+    for bid in accounts.bids.chunks(ACCOUNTS_PER_BID) {
+        let (bidder, bidder_token, bidder_pot, bidder_pot_token, bidder_meta) =
+            (&bid[0], &bid[1], &bid[2], &bid[3], &bid[4]);
+
+        assert_owned_by(bidder_meta, program_id)?;
+        assert_owned_by(bidder_pot, program_id)?;
+        assert_derivation(
+            program_id,
+            bidder_meta,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                bidder.key.as_ref(),
+                "metadata".as_bytes(),
+            ],
+        )?;
+        assert_derivation(
+            program_id,
+            bidder_pot,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                bidder.key.as_ref(),
+            ],
+        )?;
+
+        let metadata = BidderMetadata::from_account_info(bidder_meta)?;
+        let bidder_pot_data = BidderPot::from_account_info(bidder_pot)?;
+        if bidder_pot_data.bidder_pot != *bidder_pot_token.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+
+        // Bids already withdrawn may have been refunded by the bidder since the crank was sent,
+        // and winners are left for ClaimBid.
+        if metadata.cancelled
+            || auction
+                .is_winning_bid(&Bid(*bidder.key, metadata.last_bid))
+                .is_some()
+        {
+            msg!("Skipping bid of {:?}", bidder.key);
+            continue;
+        }
+
+        // Refunds only ever go to the wallet that placed the bid.
+        if native {
+            if *bidder_token.key != metadata.bidder_pubkey {
+                return Err(AuctionError::InvalidBidOwner.into());
+            }
+        } else {
+            assert_owned_by(bidder_token, accounts.token_program.key)?;
+            let token_account = unpack_token_account(bidder_token)?;
+            if token_account.owner != metadata.bidder_pubkey
+                || token_account.mint != auction.token_mint
+            {
+                return Err(AuctionError::InvalidBidOwner.into());
+            }
+        }
+
+        let balance = pot_balance(native, accounts.auction, bidder_pot, bidder_pot_token)?;
+        let reward = (balance as u128)
+            .checked_mul(auction_extended.crank_reward_basis_points as u128)
+            .ok_or(AuctionError::NumericalOverflowError)?
+            / 10000;
+        let reward = reward as u64;
+
+        for (destination, amount) in vec![
+            (bidder_token, balance - reward),
+            (accounts.cranker_token, reward),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            if native {
+                native_pot_withdraw(bidder_pot, destination, amount)?;
+            } else {
+                spl_token_transfer(TokenTransferParams {
+                    source: bidder_pot_token.clone(),
+                    destination: destination.clone(),
+                    mint: accounts.mint.clone(),
+                    authority: accounts.auction.clone(),
+                    authority_signer_seeds: auction_seeds,
+                    token_program: accounts.token_program.clone(),
+                    amount,
+                })?;
+            }
+        }
+
+        BidderMetadata {
+            cancelled: true,
+            ..metadata
+        }
+        .serialize(&mut *bidder_meta.data.borrow_mut())?;
+        auction_extended.pot_drained();
+    }
+
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+//! Refunds a winning bidder whatever their pot holds above the clearing price, once an auction
+//! where every winner pays the same price has ended. This works whether or not the authority has
+//! already claimed the clearing price out of the pot, but only once, the bidder metadata is marked
+//! cancelled to record it.
+//!
+//! Bids a wallet holds beyond its first are refunded by passing their bid key as the bidder and the
+//! wallet as the bid owner.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, BidderMetadata, BidderPot},
+    utils::{
+        assert_bid_owner, assert_derivation, assert_owned_by, assert_token_program_matches_package,
+        spl_token_transfer, unpack_token_account, TokenTransferParams,
+    },
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct RefundExcessArgs {
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    bidder_token: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+    bid_owner: Option<&'a AccountInfo<'b>>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_token: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        bid_owner: next_account_info(account_iter).ok(),
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.mint, accounts.token_program.key)?;
+    assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_owned_by(accounts.bidder_meta, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_pot_token, accounts.token_program.key)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    Ok(accounts)
+}
+
+pub fn refund_excess(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RefundExcessArgs,
+) -> ProgramResult {
+    msg!("+ Processing RefundExcess");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    // The account within the pot must be owned by us.
+    let actual_account = unpack_token_account(accounts.bidder_pot_token)?;
+    if actual_account.owner != *accounts.auction.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    // Derive and load Auction.
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    let auction_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        &[auction_bump],
+    ];
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+
+    // The mint provided must match the one the auction was initialized with.
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+
+    let clearing_price = auction
+        .clearing_price()
+        .ok_or(AuctionError::NoClearingPrice)?;
+
+    // The clearing price is only final once the auction is over, and losers use CancelBid.
+    if !auction.ended(clock.unix_timestamp)? || auction.is_winner(accounts.bidder.key).is_none() {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    // Confirm we're looking at the real SPL account for this bidder.
+    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+    assert_bid_owner(accounts.bidder, accounts.bid_owner, &metadata.bidder_pubkey)?;
+    if metadata.cancelled {
+        return Err(AuctionError::BidderPotEmpty.into());
+    }
+
+    // Leave the clearing price behind until the authority has claimed it.
+    let refund = if bidder_pot.emptied {
+        actual_account.amount
+    } else {
+        actual_account.amount.saturating_sub(clearing_price)
+    };
+
+    msg!("Refunding {:?} above the clearing price", refund);
+    spl_token_transfer(TokenTransferParams {
+        source: accounts.bidder_pot_token.clone(),
+        destination: accounts.bidder_token.clone(),
+        mint: accounts.mint.clone(),
+        authority: accounts.auction.clone(),
+        authority_signer_seeds: auction_seeds,
+        token_program: accounts.token_program.clone(),
+        amount: refund,
+    })?;
+
+    BidderMetadata {
+        cancelled: true,
+        ..metadata
+    }
+    .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
+
+    // Once the authority has claimed too the pot is settled.
+    if bidder_pot.emptied {
+        assert_derivation(
+            program_id,
+            accounts.auction_extended,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                args.resource.as_ref(),
+                EXTENDED.as_bytes(),
+            ],
+        )?;
+        let mut auction_extended =
+            AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+        auction_extended.pot_drained();
+        auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+    }
+
+    Ok(())
+}
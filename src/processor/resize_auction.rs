@@ -0,0 +1,259 @@
+//! Raises the number of winners of a capped auction, or the number of bids it holds before the
+//! lowest drops out, while it is still taking bids. The auction account grows to fit, with the
+//! payer topping up its rent. An account can only grow by 10KB in one instruction, room for 256
+//! bids, so larger raises are sent as several smaller ones: up to 128 more winners at a time, as
+//! each winner keeps room for two bids, or up to 256 more bids at a time through max_bids.
+//!
+//! The winner limit can also come down, but never below the bids already winning. An auction that
+//! is over, by its state or by its end time, can no longer be resized.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, BASE_AUCTION_DATA_SIZE,
+    },
+    utils::{assert_derivation, assert_owned_by, assert_signer, resize_account_raw},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+    std::{cmp, mem},
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct ResizeAuctionArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+    /// How many winners are allowed from now on.
+    pub winners: usize,
+    /// Bids to hold before the lowest drops out, never less than a new auction of as many winners
+    /// or than the auction already holds.
+    pub max_bids: Option<usize>,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        payer: next_account_info(account_iter)?,
+        rent: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.authority)?;
+    assert_signer(accounts.payer)?;
+    Ok(accounts)
+}
+
+pub fn resize_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: ResizeAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing ResizeAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Check authority is correct.
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    // Bids still have to be coming in, a Started auction past its end time is over already.
+    match auction.state {
+        AuctionState::Created | AuctionState::Started => (),
+        _ => return Err(AuctionError::InvalidState.into()),
+    }
+    if auction.ended(clock.unix_timestamp)? {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    let bid_capacity = auction.bid_capacity(accounts.auction);
+    let trailing_slots = auction.bid_state.trailing_slots();
+    let (bids, max) = match &mut auction.bid_state {
+        BidState::EnglishAuction { bids, max }
+        | BidState::SealedBid { bids, max, .. }
+        | BidState::UniformPriceAuction { bids, max, .. } => (bids, max),
+        // Dutch auctions have a single winner and open editions keep no bids.
+        _ => return Err(AuctionError::InvalidState.into()),
+    };
+
+    // Bids winning now must still win.
+    if args.winners == 0 || args.winners < cmp::min(bids.len(), *max) {
+        return Err(AuctionError::InvalidWinnerLimit.into());
+    }
+    // Holding several bids only helps a wallet where several bids can win.
+    if auction_extended.max_bids_per_bidder.is_some() && args.winners < 2 {
+        return Err(AuctionError::InvalidBidLimit.into());
+    }
+    *max = args.winners;
+
+    let new_capacity = cmp::max(
+        cmp::max(bid_capacity, BidState::max_array_size_for(args.winners)),
+        args.max_bids.unwrap_or(0),
+    );
+    let size = new_capacity
+        .checked_add(trailing_slots)
+        .and_then(|slots| slots.checked_mul(mem::size_of::<Bid>()))
+        .and_then(|bids_size| bids_size.checked_add(BASE_AUCTION_DATA_SIZE))
+        .ok_or(AuctionError::NumericalOverflowError)?;
+    if size > accounts.auction.data_len() {
+        if size - accounts.auction.data_len() > MAX_PERMITTED_DATA_INCREASE {
+            msg!(
+                "Growing the auction to {:?} bytes takes several ResizeAuction instructions",
+                size
+            );
+            return Err(AuctionError::AuctionGrowthTooLarge.into());
+        }
+        resize_account_raw(
+            accounts.auction,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            size,
+        )?;
+    }
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::processor::{
+            tests::{self, extended, pda, TestAccount},
+            MAX_AUCTION_DATA_EXTENDED_SIZE,
+        },
+        solana_program::{borsh::try_from_slice_unchecked, system_program, sysvar},
+    };
+
+    /// Accounts of a ResizeAuction on an English auction of one winner, with room for eight bids,
+    /// that started and runs until the given time.
+    fn resize_accounts(
+        program_id: &Pubkey,
+        resource: &Pubkey,
+        ended_at: i64,
+        now: i64,
+    ) -> Vec<TestAccount> {
+        let authority = Pubkey::new_unique();
+        let auction = AuctionData {
+            authority,
+            ended_at: Some(ended_at),
+            ..tests::auction(BidState::new_english(1))
+        };
+        vec![
+            TestAccount::signer(authority),
+            TestAccount::with(
+                pda(
+                    program_id,
+                    &[PREFIX.as_bytes(), program_id.as_ref(), resource.as_ref()],
+                ),
+                *program_id,
+                &auction,
+                BASE_AUCTION_DATA_SIZE + 8 * mem::size_of::<Bid>(),
+            ),
+            TestAccount::with(
+                pda(
+                    program_id,
+                    &[
+                        PREFIX.as_bytes(),
+                        program_id.as_ref(),
+                        resource.as_ref(),
+                        EXTENDED.as_bytes(),
+                    ],
+                ),
+                *program_id,
+                &extended(),
+                MAX_AUCTION_DATA_EXTENDED_SIZE,
+            ),
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(sysvar::rent::id(), sysvar::id(), vec![]),
+            TestAccount::new(system_program::id(), Pubkey::default(), vec![]),
+            TestAccount::clock(now),
+        ]
+    }
+
+    fn resize(accounts: &mut [TestAccount], resource: Pubkey, winners: usize) -> ProgramResult {
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        resize_auction(
+            &crate::id(),
+            &infos,
+            ResizeAuctionArgs {
+                resource,
+                winners,
+                max_bids: None,
+            },
+        )
+    }
+
+    #[test]
+    fn auction_past_its_end_time_cannot_be_resized() {
+        let program_id = crate::id();
+        let resource = Pubkey::new_unique();
+
+        // Still in the Started state, but its end time has passed.
+        let mut accounts = resize_accounts(&program_id, &resource, 100, 101);
+        assert_eq!(
+            resize(&mut accounts, resource, 2),
+            Err(AuctionError::InvalidState.into())
+        );
+
+        // Up to its end time the winners can still be raised, within the room it already has.
+        let mut accounts = resize_accounts(&program_id, &resource, 100, 100);
+        resize(&mut accounts, resource, 2).unwrap();
+        let auction: AuctionData = try_from_slice_unchecked(&accounts[1].data).unwrap();
+        assert_eq!(auction.bid_state.num_possible_winners(), 2);
+    }
+}
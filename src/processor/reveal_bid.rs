@@ -0,0 +1,205 @@
+//! Reveals a sealed bid once the commit phase of a sealed-bid auction is over. The revealed amount
+//! must hash to the commitment and be covered by the tokens already held in the bidder pot, it is
+//! then ranked against the other revealed bids.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, Bid, BidState, BidderCommitment, BidderMetadata,
+        BidderPot,
+    },
+    utils::{assert_derivation, assert_owned_by, assert_signer, pot_balance},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        hash, msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+};
+
+/// Arguments for the RevealBid instruction discriminant .
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct RevealBidArgs {
+    /// Amount that was committed to.
+    pub amount: u64,
+    /// Salt that was hashed alongside the amount.
+    pub salt: u64,
+    /// Resource being bid on.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_commitment: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        bidder_commitment: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_owned_by(accounts.bidder_meta, program_id)?;
+    assert_owned_by(accounts.bidder_commitment, program_id)?;
+    assert_signer(accounts.bidder)?;
+
+    Ok(accounts)
+}
+
+pub fn reveal_bid<'r, 'b: 'r>(
+    program_id: &Pubkey,
+    accounts: &'r [AccountInfo<'b>],
+    args: RevealBidArgs,
+) -> ProgramResult {
+    msg!("+ Processing RevealBid");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    // Derive and load Auction.
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+
+    // Load the clock, bids are only revealed between the end time and the end of the reveal window.
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+    match auction.bid_state {
+        BidState::SealedBid { .. } => (),
+        _ => return Err(AuctionError::NotSealedBidAuction.into()),
+    }
+    if !auction.in_reveal_phase(clock.unix_timestamp)? {
+        return Err(AuctionError::InvalidSealedBidPhase.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+    if metadata.cancelled {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_commitment,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "commitment".as_bytes(),
+        ],
+    )?;
+    let mut commitment = BidderCommitment::from_account_info(accounts.bidder_commitment)?;
+    if commitment.revealed {
+        return Err(AuctionError::BidAlreadyRevealed.into());
+    }
+
+    let reveal_hash = hash::hashv(&[&args.amount.to_be_bytes(), &args.salt.to_be_bytes()]);
+    if reveal_hash != commitment.commitment {
+        return Err(AuctionError::InvalidReveal.into());
+    }
+
+    // Confirm we're looking at the real SPL account for this bidder and that it backs the bid.
+    assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    let balance = pot_balance(
+        false,
+        accounts.auction,
+        accounts.bidder_pot,
+        accounts.bidder_pot_token,
+    )?;
+    if balance < args.amount {
+        msg!(
+            "Revealed bid {:?} is not covered by the pot balance of {:?}",
+            args.amount,
+            balance
+        );
+        return Err(AuctionError::BalanceTooLow.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    let bid_capacity = auction.bid_capacity(accounts.auction);
+    auction.reveal_bid(
+        Bid(*accounts.bidder.key, args.amount),
+        auction_extended.tick_size,
+        bid_capacity,
+    )?;
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    commitment.revealed = true;
+    commitment.serialize(&mut *accounts.bidder_commitment.data.borrow_mut())?;
+
+    BidderMetadata {
+        last_bid: args.amount,
+        last_bid_timestamp: clock.unix_timestamp,
+        ..metadata
+    }
+    .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
-//! Resets authority on an auction account.
+//! Resets authority on an auction account in one step. ProposeAuthority and AcceptAuthority are
+//! safer, as a mistyped key here locks the auction for good. Roles delegated by the old authority
+//! lapse with the change.
 
 use crate::{
     errors::AuctionError,
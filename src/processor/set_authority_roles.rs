@@ -0,0 +1,99 @@
+//! Delegates narrower roles so hot keys need not hold full authority over an auction. The claimer
+//! can send ClaimBid, and so choose where winnings go, the starter StartAuction and EndAuction.
+//! Both are given in full each time, none removing a role. Roles lapse when the authority changes.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended},
+    utils::{assert_derivation, assert_owned_by, assert_signer},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct SetAuthorityRolesArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+    /// Key allowed to claim bids.
+    pub claimer: Option<Pubkey>,
+    /// Key allowed to start and end the auction.
+    pub starter: Option<Pubkey>,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.authority)?;
+    Ok(accounts)
+}
+
+pub fn set_authority_roles<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: SetAuthorityRolesArgs,
+) -> ProgramResult {
+    msg!("+ Processing SetAuthorityRoles");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Check authority is correct.
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    auction_extended.delegate_for(&auction.authority);
+    auction_extended.claimer = args.claimer;
+    auction_extended.starter = args.starter;
+
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
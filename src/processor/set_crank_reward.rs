@@ -0,0 +1,112 @@
+//! Sets the share of each losing bid paid to whoever refunds it with RefundBids. Bidders bid
+//! knowing the reward, so it can only be set before the auction starts, and never above
+//! MAX_CRANK_REWARD_BASIS_POINTS.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, AuctionState, MAX_CRANK_REWARD_BASIS_POINTS},
+    utils::{assert_derivation, assert_owned_by, assert_signer},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct SetCrankRewardArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+    /// Share of each refund paid to the sender of RefundBids, in basis points.
+    pub crank_reward_basis_points: u16,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.authority)?;
+    Ok(accounts)
+}
+
+pub fn set_crank_reward<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: SetCrankRewardArgs,
+) -> ProgramResult {
+    msg!("+ Processing SetCrankReward");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Check authority is correct.
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    // A scheduled auction is running once its start time has passed, whether or not a bid has
+    // moved it to Started yet.
+    let scheduled_start_passed = match auction_extended.start_auction_at {
+        Some(start) => start <= clock.unix_timestamp,
+        None => false,
+    };
+    if auction.state != AuctionState::Created || scheduled_start_passed {
+        return Err(AuctionError::InvalidState.into());
+    }
+    if args.crank_reward_basis_points > MAX_CRANK_REWARD_BASIS_POINTS {
+        return Err(AuctionError::InvalidCrankReward.into());
+    }
+
+    auction_extended.crank_reward_basis_points = args.crank_reward_basis_points;
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionState, Bid, BidState, WinnerLimit},
-    utils::{assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw},
-    PREFIX,
+    processor::{AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, WinnerLimit},
+    utils::{
+        assert_authority_or_role, assert_derivation, assert_owned_by, assert_signer,
+        create_or_allocate_account_raw,
+    },
+    EXTENDED, PREFIX,
 };
 
 use {
@@ -23,6 +26,7 @@ struct Accounts<'a, 'b: 'a> {
     authority: &'a AccountInfo<'b>,
     auction: &'a AccountInfo<'b>,
     clock_sysvar: &'a AccountInfo<'b>,
+    auction_extended: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -34,9 +38,13 @@ fn parse_accounts<'a, 'b: 'a>(
         authority: next_account_info(account_iter)?,
         auction: next_account_info(account_iter)?,
         clock_sysvar: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter).ok(),
     };
     assert_owned_by(accounts.auction, program_id)?;
     assert_signer(accounts.authority)?;
+    if let Some(auction_extended) = accounts.auction_extended {
+        assert_owned_by(auction_extended, program_id)?;
+    }
     Ok(accounts)
 }
 
@@ -67,30 +75,44 @@ pub fn start_auction<'a, 'b: 'a>(
         ],
     )?;
 
-    // Initialise a new auction. The end time is calculated relative to now.
+    // Initialise a new auction. The end time is calculated relative to now, or to the scheduled
+    // start time if there is one.
     let mut auction = AuctionData::from_account_info(accounts.auction)?;
 
-    // Check authority is correct.
-    if auction.authority != *accounts.authority.key {
-        return Err(AuctionError::InvalidAuthority.into());
-    }
-
-    // Calculate the relative end time.
-    let ended_at = if let Some(end_auction_at) = auction.end_auction_at {
-        match clock.unix_timestamp.checked_add(end_auction_at) {
-            Some(val) => Some(val),
-            None => return Err(AuctionError::NumericalOverflowError.into()),
+    // The starter the authority delegated to and the scheduled start time are only known when the
+    // extended account is passed. Without it only the authority may start the auction, as callers
+    // did before scheduling existed, unless it already has the end time that scheduling gives it.
+    let (starter, start_auction_at) = match accounts.auction_extended {
+        Some(auction_extended) => {
+            assert_derivation(
+                program_id,
+                auction_extended,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    args.resource.as_ref(),
+                    EXTENDED.as_bytes(),
+                ],
+            )?;
+            let auction_extended = AuctionDataExtended::from_account_info(auction_extended)?;
+            (
+                auction_extended.delegate(&auction.authority, auction_extended.starter),
+                auction_extended.start_auction_at,
+            )
         }
-    } else {
-        None
+        None if auction.state == AuctionState::Created && auction.ended_at.is_some() => {
+            return Err(AuctionError::AuctionNotYetStarted.into());
+        }
+        None => (None, None),
     };
 
-    AuctionData {
-        ended_at,
-        state: auction.state.start()?,
-        ..auction
-    }
-    .serialize(&mut *accounts.auction.data.borrow_mut())?;
+    // Check authority is correct, or the starter it delegated to.
+    assert_authority_or_role(&auction.authority, starter, accounts.authority.key)?;
+
+    // Start the auction, calculating the relative end time. A scheduled auction cannot be started
+    // before its start time.
+    auction.start(clock.unix_timestamp, start_auction_at)?;
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
 
     Ok(())
 }
@@ -0,0 +1,88 @@
+//! Starts an auction that was created with a start time once that time has passed. Anyone can send
+//! this, it only persists the transition that bidding would otherwise make on first use, so that
+//! the Started state is visible to programs reading the auction account directly.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, StartAuctionArgs},
+    utils::{assert_derivation, assert_owned_by},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    Ok(accounts)
+}
+
+pub fn start_scheduled_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: StartAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing StartScheduledAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Only the schedule can start the auction here, StartAuction remains the authority's path.
+    if auction_extended.start_auction_at.is_none() {
+        return Err(AuctionError::AuctionNotScheduled.into());
+    }
+
+    auction.start(clock.unix_timestamp, auction_extended.start_auction_at)?;
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
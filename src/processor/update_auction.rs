@@ -0,0 +1,175 @@
+//! Rewrites the settings of an auction that has not started yet, so a mistake made at creation
+//! does not mean abandoning the resource. The settings are given in full, as to CreateAuction, and
+//! checked the same way. Once an auction has started it can still have its end time pushed back,
+//! but only while nobody has bid, nothing else can change.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, AuctionName, AuctionState, PriceFloor},
+    utils::{assert_derivation, assert_owned_by, assert_signer},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::UnixTimestamp,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct UpdateAuctionArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+    /// End time is the cut-off point that the auction is forced to end by. See AuctionData.
+    pub end_auction_at: Option<UnixTimestamp>,
+    /// Gap time is how much time after the previous bid where the auction ends. See AuctionData.
+    pub end_auction_gap: Option<UnixTimestamp>,
+    /// Set a price floor.
+    pub price_floor: PriceFloor,
+    /// Add a tick size increment
+    pub tick_size: Option<u64>,
+    /// Add a minimum percentage increase each bid must meet.
+    pub gap_tick_size_percentage: Option<u8>,
+    /// Add a instant sale price.
+    pub instant_sale_price: Option<u64>,
+    /// Auction name
+    pub name: Option<AuctionName>,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.authority)?;
+    Ok(accounts)
+}
+
+pub fn update_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: UpdateAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing UpdateAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Check authority is correct.
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    auction.bid_state.assert_valid_settings(
+        args.end_auction_at,
+        args.tick_size,
+        args.gap_tick_size_percentage,
+        args.instant_sale_price,
+        &auction_extended.anti_sniping,
+    )?;
+
+    // A scheduled auction is running once its start time has passed, whether or not a bid has
+    // moved it to Started yet.
+    let scheduled_start_passed = match auction_extended.start_auction_at {
+        Some(start) => start <= clock.unix_timestamp,
+        None => false,
+    };
+
+    match auction.state {
+        AuctionState::Created if !scheduled_start_passed => {
+            auction.end_auction_at = args.end_auction_at;
+            auction.end_auction_gap = args.end_auction_gap;
+            auction.price_floor = args.price_floor;
+            auction_extended.tick_size = args.tick_size;
+            auction_extended.gap_tick_size_percentage = args.gap_tick_size_percentage;
+            auction_extended.instant_sale_price = args.instant_sale_price;
+            auction_extended.name = args.name;
+
+            // A scheduled auction already knows when it ends.
+            if let Some(start) = auction_extended.start_auction_at {
+                auction.ended_at = auction.end_time_from(start)?;
+            }
+        }
+        AuctionState::Created | AuctionState::Started => {
+            // Bidders may have seen the auction by now, so only its end time can move, and only
+            // later, before anyone has bid.
+            let no_bids = auction.last_bid.is_none() && auction_extended.funded_pots == 0;
+            let unchanged = args.end_auction_gap == auction.end_auction_gap
+                && args.price_floor == auction.price_floor
+                && args.tick_size == auction_extended.tick_size
+                && args.gap_tick_size_percentage == auction_extended.gap_tick_size_percentage
+                && args.instant_sale_price == auction_extended.instant_sale_price
+                && args.name == auction_extended.name;
+            let (old_end, new_end) = match (auction.end_auction_at, args.end_auction_at) {
+                (Some(old_end), Some(new_end)) if new_end >= old_end => (old_end, new_end),
+                _ => return Err(AuctionError::InvalidState.into()),
+            };
+            if !no_bids || !unchanged || auction.ended(clock.unix_timestamp)? {
+                return Err(AuctionError::InvalidState.into());
+            }
+
+            auction.end_auction_at = Some(new_end);
+            auction.ended_at = match auction.ended_at {
+                Some(ended_at) => Some(
+                    ended_at
+                        .checked_add(new_end - old_end)
+                        .ok_or(AuctionError::NumericalOverflowError)?,
+                ),
+                None => None,
+            };
+        }
+        AuctionState::Ended | AuctionState::Paused | AuctionState::Cancelled => {
+            return Err(AuctionError::InvalidState.into())
+        }
+    }
+
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use solana_program::program_pack::IsInitialized;
 
 use {
-    crate::errors::AuctionError,
+    crate::{errors::AuctionError, token_2022},
     solana_program::{
         account_info::AccountInfo,
         entrypoint::ProgramResult,
@@ -10,9 +10,10 @@ use {
         program_error::ProgramError,
         program_pack::Pack,
         pubkey::Pubkey,
-        system_instruction,
+        system_instruction, system_program,
         sysvar::{rent::Rent, Sysvar},
     },
+    spl_token::state::{Account, Mint},
     std::convert::TryInto,
 };
 
@@ -28,13 +29,53 @@ pub fn assert_initialized<T: Pack + IsInitialized>(
 }
 
 pub fn assert_token_program_matches_package(token_program_info: &AccountInfo) -> ProgramResult {
-    if *token_program_info.key != spl_token::id() {
+    if *token_program_info.key != spl_token::id() && *token_program_info.key != token_2022::id() {
         return Err(AuctionError::InvalidTokenProgram.into());
     }
 
     Ok(())
 }
 
+// Token-2022 accounts and mints carrying extensions hold a type byte after the size of a token
+// account, followed by the extensions.
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// Unpacks the base state of a token account of either token program.
+pub fn unpack_token_account(account_info: &AccountInfo) -> Result<Account, ProgramError> {
+    let data = account_info.data.borrow();
+    if data.len() < Account::LEN
+        || (data.len() > Account::LEN && data[Account::LEN] != ACCOUNT_TYPE_ACCOUNT)
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let account = Account::unpack_unchecked(&data[..Account::LEN])?;
+    if !account.is_initialized() {
+        return Err(AuctionError::Uninitialized.into());
+    }
+
+    Ok(account)
+}
+
+/// Unpacks the base state of a mint of either token program.
+pub fn unpack_mint(mint_info: &AccountInfo) -> Result<Mint, ProgramError> {
+    let data = mint_info.data.borrow();
+    if data.len() < Mint::LEN
+        || (data.len() > Mint::LEN
+            && (data.len() <= Account::LEN || data[Account::LEN] != ACCOUNT_TYPE_MINT))
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mint = Mint::unpack_unchecked(&data[..Mint::LEN])?;
+    if !mint.is_initialized() {
+        return Err(AuctionError::Uninitialized.into());
+    }
+
+    Ok(mint)
+}
+
 pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
     if account.owner != owner {
         msg!(
@@ -65,6 +106,38 @@ pub fn assert_signer(account_info: &AccountInfo) -> ProgramResult {
     }
 }
 
+/// Confirm the wallet that placed a bid is acting on it. A wallets first bid is keyed by the wallet
+/// itself, which signs as the bidder, any further bids by a PDA that cannot sign, the wallet
+/// recorded in their metadata then signing as the bid owner instead.
+pub fn assert_bid_owner<'a>(
+    bidder_info: &AccountInfo<'a>,
+    bid_owner_info: Option<&AccountInfo<'a>>,
+    bid_owner: &Pubkey,
+) -> ProgramResult {
+    let signer = if bidder_info.is_signer {
+        bidder_info
+    } else {
+        bid_owner_info.ok_or(ProgramError::MissingRequiredSignature)?
+    };
+    assert_signer(signer)?;
+    if signer.key != bid_owner {
+        return Err(AuctionError::InvalidBidOwner.into());
+    }
+    Ok(())
+}
+
+/// Confirm the signer is the auction authority or the key it delegated a role to.
+pub fn assert_authority_or_role(
+    authority: &Pubkey,
+    role: Option<Pubkey>,
+    signer: &Pubkey,
+) -> ProgramResult {
+    if authority != signer && role.as_ref() != Some(signer) {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+    Ok(())
+}
+
 pub fn assert_derivation(
     program_id: &Pubkey,
     account: &AccountInfo,
@@ -123,12 +196,147 @@ pub fn create_or_allocate_account_raw<'a>(
     Ok(())
 }
 
+/// Grows a program owned account to the given size, topping up its rent exemption from the payer.
+/// The new space is zeroed.
+#[inline(always)]
+pub fn resize_account_raw<'a>(
+    account_info: &AccountInfo<'a>,
+    rent_sysvar_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    size: usize,
+) -> ProgramResult {
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+    let required_lamports = rent
+        .minimum_balance(size)
+        .saturating_sub(account_info.lamports());
+
+    if required_lamports > 0 {
+        msg!(
+            "Transfer {} lamports to the resized account",
+            required_lamports
+        );
+        invoke(
+            &system_instruction::transfer(&payer_info.key, account_info.key, required_lamports),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+
+    account_info.realloc(size, true)?;
+
+    Ok(())
+}
+
+/// Counterpart to create_or_allocate_account_raw, moves all lamports of a program owned account to
+/// the receiver and hands it back to the system program with no data. Refunding the account later
+/// in the same transaction cannot bring back the old state.
+#[inline(always)]
+pub fn close_account_raw<'a>(
+    account_info: &AccountInfo<'a>,
+    receiver_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let lamports = account_info.lamports();
+    msg!("Return {} lamports from the closed account", lamports);
+    **receiver_info.lamports.borrow_mut() = receiver_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+    **account_info.lamports.borrow_mut() = 0;
+
+    account_info.data.borrow_mut().fill(0);
+    account_info.realloc(0, false)?;
+    account_info.assign(&system_program::id());
+
+    Ok(())
+}
+
+/// Balance held by a bidder pot. Native SOL auctions keep bids as lamports in the pot itself, on
+/// top of its rent exemption, other auctions keep them in an SPL account owned by the auction.
+pub fn pot_balance(
+    native: bool,
+    auction_info: &AccountInfo,
+    bidder_pot_info: &AccountInfo,
+    bidder_pot_token_info: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    if native {
+        if bidder_pot_token_info.key != bidder_pot_info.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+        let rent = Rent::get()?;
+        return Ok(bidder_pot_info
+            .lamports()
+            .saturating_sub(rent.minimum_balance(bidder_pot_info.data_len())));
+    }
+
+    // The account within the pot must be owned by us.
+    if *bidder_pot_token_info.owner != spl_token::id()
+        && *bidder_pot_token_info.owner != token_2022::id()
+    {
+        return Err(AuctionError::IncorrectOwner.into());
+    }
+    let actual_account = unpack_token_account(bidder_pot_token_info)?;
+    if actual_account.owner != *auction_info.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    Ok(actual_account.amount)
+}
+
+/// Moves lamports from a signing wallet into the bidder pot of a native SOL auction.
+#[inline(always)]
+pub fn native_pot_deposit<'a>(
+    source_info: &AccountInfo<'a>,
+    bidder_pot_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    invoke(
+        &system_instruction::transfer(source_info.key, bidder_pot_info.key, amount),
+        &[
+            source_info.clone(),
+            bidder_pot_info.clone(),
+            system_program_info.clone(),
+        ],
+    )
+}
+
+/// Pays lamports out of the bidder pot of a native SOL auction. The pot is owned by the program so
+/// they are moved directly, never taking the pot below its rent exemption.
+#[inline(always)]
+pub fn native_pot_withdraw(
+    bidder_pot_info: &AccountInfo,
+    destination_info: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let available = bidder_pot_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(bidder_pot_info.data_len()));
+    if amount > available {
+        return Err(AuctionError::BalanceTooLow.into());
+    }
+
+    **bidder_pot_info.lamports.borrow_mut() = bidder_pot_info.lamports() - amount;
+    **destination_info.lamports.borrow_mut() = destination_info
+        .lamports()
+        .checked_add(amount)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+
+    Ok(())
+}
+
 ///TokenTransferParams
 pub struct TokenTransferParams<'a: 'b, 'b> {
     /// source
     pub source: AccountInfo<'a>,
     /// destination
     pub destination: AccountInfo<'a>,
+    /// mint
+    pub mint: AccountInfo<'a>,
     /// amount
     pub amount: u64,
     /// authority
@@ -144,28 +352,79 @@ pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult
     let TokenTransferParams {
         source,
         destination,
+        mint,
         authority,
         token_program,
         amount,
         authority_signer_seeds,
     } = params;
 
+    let decimals = unpack_mint(&mint)?.decimals;
+    let mut instruction = spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        source.key,
+        mint.key,
+        destination.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    // Token-2022 shares the instruction layout of the original token program.
+    instruction.program_id = *token_program.key;
+
     let result = invoke_signed(
-        &spl_token::instruction::transfer(
-            token_program.key,
-            source.key,
-            destination.key,
-            authority.key,
-            &[],
-            amount,
-        )?,
-        &[source, destination, authority, token_program],
+        &instruction,
+        &[source, mint, destination, authority, token_program],
         &[authority_signer_seeds],
     );
 
     result.map_err(|_| AuctionError::TokenTransferFailed.into())
 }
 
+///TokenCloseParams
+pub struct TokenCloseParams<'a: 'b, 'b> {
+    /// account
+    pub account: AccountInfo<'a>,
+    /// destination
+    pub destination: AccountInfo<'a>,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: &'b [&'b [u8]],
+    /// token_program
+    pub token_program: AccountInfo<'a>,
+}
+
+#[inline(always)]
+pub fn spl_token_close(params: TokenCloseParams<'_, '_>) -> ProgramResult {
+    let TokenCloseParams {
+        account,
+        destination,
+        authority,
+        authority_signer_seeds,
+        token_program,
+    } = params;
+
+    let mut instruction = spl_token::instruction::close_account(
+        &spl_token::id(),
+        account.key,
+        destination.key,
+        authority.key,
+        &[],
+    )?;
+    // Token-2022 shares the instruction layout of the original token program.
+    instruction.program_id = *token_program.key;
+
+    let result = invoke_signed(
+        &instruction,
+        &[account, destination, authority, token_program],
+        &[authority_signer_seeds],
+    );
+
+    result.map_err(|_| AuctionError::TokenCloseFailed.into())
+}
+
 /// TokenMintToParams
 pub struct TokenCreateAccount<'a> {
     /// payer
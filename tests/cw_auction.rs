@@ -0,0 +1,30 @@
+//! Runs the cw-auction tool without a cluster or a keypair file, through its dry run.
+
+use {
+    cw_marketplace::PREFIX,
+    solana_program::pubkey::Pubkey,
+    std::{env, process::Command},
+};
+
+#[test]
+fn dry_run_prints_the_instruction_without_a_keypair() {
+    let program_id = cw_marketplace::id();
+    let resource = Pubkey::new_unique();
+    let (auction, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), resource.as_ref()],
+        &program_id,
+    );
+
+    // A home without a Solana config, so there is no default keypair to fall back on.
+    let home = env::temp_dir().join("cw-auction-dry-run");
+    let output = Command::new(env!("CARGO_BIN_EXE_cw-auction"))
+        .env("HOME", &home)
+        .args(["--dry-run", "start", "--resource", &resource.to_string()])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("Auction:          {}", auction)));
+    assert!(stdout.contains(&format!("Instruction for {}", program_id)));
+}